@@ -0,0 +1,82 @@
+use std::{
+    env,
+    fs,
+    io::Write,
+    path::{
+        Path,
+        PathBuf
+    },
+    hash::{
+        Hash,
+        Hasher
+    },
+    collections::hash_map::DefaultHasher
+};
+use flate2::{
+    write::GzEncoder,
+    Compression
+};
+
+/// Embeds `web/` into the bridge, as the `ASSETS` table of `src/assets.rs`.
+fn main() {
+    println!("cargo:rerun-if-changed=web");
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("web");
+    let mut files = Vec::new();
+
+    // The browser client doesn't serve itself
+    if env::var("CARGO_CFG_TARGET_ARCH").as_deref() != Ok("wasm32") {
+        walk(&root, &mut files);
+    }
+    files.sort();
+
+    let mut code = String::from("&[\n");
+
+    for (idx, file) in files.iter().enumerate() {
+        let body = fs::read(file).unwrap();
+        let path = file.strip_prefix(&root).unwrap().components()
+            .map(|x| x.as_os_str().to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let mut hasher = DefaultHasher::new();
+        body.hash(&mut hasher);
+        let etag = format!("\"{:016x}\"", hasher.finish());
+
+        let mut gz = GzEncoder::new(Vec::new(), Compression::best());
+        gz.write_all(&body).unwrap();
+        let gz = gz.finish().unwrap();
+
+        // Only kept when it pays off, images and the like are already compressed
+        let gzip = if gz.len() < body.len() * 9 / 10 {
+            let gz_file = out.join(format!("asset{idx}.gz"));
+            fs::write(&gz_file, gz).unwrap();
+            format!("Some(include_bytes!({:?}))", gz_file.display().to_string())
+        } else {
+            "None".to_owned()
+        };
+
+        code += &format!(
+            "    Asset {{ path: {:?}, etag: {etag:?}, body: include_bytes!({:?}), gzip: {gzip} }},\n",
+            format!("/{path}"), file.display().to_string()
+        );
+    }
+
+    code += "]\n";
+    fs::write(out.join("assets.rs"), code).unwrap();
+}
+
+fn walk(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+
+        if path.is_dir() {
+            walk(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
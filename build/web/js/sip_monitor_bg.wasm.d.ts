@@ -1,64 +0,0 @@
-/* tslint:disable */
-/* eslint-disable */
-export const memory: WebAssembly.Memory;
-export function start(a: number, b: number): number;
-export function main(a: number, b: number): number;
-export function wgpu_compute_pass_set_pipeline(a: number, b: number): void;
-export function wgpu_compute_pass_set_bind_group(a: number, b: number, c: number, d: number, e: number): void;
-export function wgpu_compute_pass_set_push_constant(a: number, b: number, c: number, d: number): void;
-export function wgpu_compute_pass_insert_debug_marker(a: number, b: number, c: number): void;
-export function wgpu_compute_pass_push_debug_group(a: number, b: number, c: number): void;
-export function wgpu_compute_pass_pop_debug_group(a: number): void;
-export function wgpu_compute_pass_write_timestamp(a: number, b: number, c: number): void;
-export function wgpu_compute_pass_begin_pipeline_statistics_query(a: number, b: number, c: number): void;
-export function wgpu_compute_pass_end_pipeline_statistics_query(a: number): void;
-export function wgpu_compute_pass_dispatch_workgroups(a: number, b: number, c: number, d: number): void;
-export function wgpu_compute_pass_dispatch_workgroups_indirect(a: number, b: number, c: number): void;
-export function wgpu_render_bundle_set_pipeline(a: number, b: number): void;
-export function wgpu_render_bundle_set_bind_group(a: number, b: number, c: number, d: number, e: number): void;
-export function wgpu_render_bundle_set_vertex_buffer(a: number, b: number, c: number, d: number, e: number): void;
-export function wgpu_render_bundle_set_push_constants(a: number, b: number, c: number, d: number, e: number): void;
-export function wgpu_render_bundle_draw(a: number, b: number, c: number, d: number, e: number): void;
-export function wgpu_render_bundle_draw_indexed(a: number, b: number, c: number, d: number, e: number, f: number): void;
-export function wgpu_render_bundle_draw_indirect(a: number, b: number, c: number): void;
-export function wgpu_render_bundle_draw_indexed_indirect(a: number, b: number, c: number): void;
-export function wgpu_render_pass_set_pipeline(a: number, b: number): void;
-export function wgpu_render_pass_set_bind_group(a: number, b: number, c: number, d: number, e: number): void;
-export function wgpu_render_pass_set_vertex_buffer(a: number, b: number, c: number, d: number, e: number): void;
-export function wgpu_render_pass_set_push_constants(a: number, b: number, c: number, d: number, e: number): void;
-export function wgpu_render_pass_draw(a: number, b: number, c: number, d: number, e: number): void;
-export function wgpu_render_pass_draw_indexed(a: number, b: number, c: number, d: number, e: number, f: number): void;
-export function wgpu_render_pass_draw_indirect(a: number, b: number, c: number): void;
-export function wgpu_render_pass_draw_indexed_indirect(a: number, b: number, c: number): void;
-export function wgpu_render_pass_multi_draw_indirect(a: number, b: number, c: number, d: number): void;
-export function wgpu_render_pass_multi_draw_indexed_indirect(a: number, b: number, c: number, d: number): void;
-export function wgpu_render_pass_multi_draw_indirect_count(a: number, b: number, c: number, d: number, e: number, f: number): void;
-export function wgpu_render_pass_multi_draw_indexed_indirect_count(a: number, b: number, c: number, d: number, e: number, f: number): void;
-export function wgpu_render_pass_set_blend_constant(a: number, b: number): void;
-export function wgpu_render_pass_set_scissor_rect(a: number, b: number, c: number, d: number, e: number): void;
-export function wgpu_render_pass_set_viewport(a: number, b: number, c: number, d: number, e: number, f: number, g: number): void;
-export function wgpu_render_pass_set_stencil_reference(a: number, b: number): void;
-export function wgpu_render_pass_insert_debug_marker(a: number, b: number, c: number): void;
-export function wgpu_render_pass_push_debug_group(a: number, b: number, c: number): void;
-export function wgpu_render_pass_pop_debug_group(a: number): void;
-export function wgpu_render_pass_write_timestamp(a: number, b: number, c: number): void;
-export function wgpu_render_pass_begin_pipeline_statistics_query(a: number, b: number, c: number): void;
-export function wgpu_render_pass_end_pipeline_statistics_query(a: number): void;
-export function wgpu_render_pass_execute_bundles(a: number, b: number, c: number): void;
-export function wgpu_render_bundle_set_index_buffer(a: number, b: number, c: number, d: number, e: number): void;
-export function wgpu_render_bundle_pop_debug_group(a: number): void;
-export function wgpu_render_bundle_insert_debug_marker(a: number, b: number): void;
-export function wgpu_render_pass_set_index_buffer(a: number, b: number, c: number, d: number, e: number): void;
-export function wgpu_render_bundle_push_debug_group(a: number, b: number): void;
-export function __wbindgen_malloc(a: number): number;
-export function __wbindgen_realloc(a: number, b: number, c: number): number;
-export const __wbindgen_export_2: WebAssembly.Table;
-export function _dyn_core__ops__function__FnMut_____Output___R_as_wasm_bindgen__closure__WasmClosure___describe__invoke__h68ebb32cc590f55f(a: number, b: number): void;
-export function _dyn_core__ops__function__FnMut__A____Output___R_as_wasm_bindgen__closure__WasmClosure___describe__invoke__h142a569feec9d277(a: number, b: number, c: number): void;
-export function __wbindgen_add_to_stack_pointer(a: number): number;
-export function _dyn_core__ops__function__FnMut_____Output___R_as_wasm_bindgen__closure__WasmClosure___describe__invoke__h306a0ab212fbd8ef(a: number, b: number, c: number): void;
-export function _dyn_core__ops__function__FnMut__A____Output___R_as_wasm_bindgen__closure__WasmClosure___describe__invoke__h60d230710ceea869(a: number, b: number, c: number): void;
-export function _dyn_core__ops__function__FnMut__A____Output___R_as_wasm_bindgen__closure__WasmClosure___describe__invoke__h37fa95bf1891ed9e(a: number, b: number, c: number): void;
-export function __wbindgen_exn_store(a: number): void;
-export function wasm_bindgen__convert__closures__invoke2_mut__h129c8bb6601359b3(a: number, b: number, c: number, d: number): void;
-export function __wbindgen_start(): void;
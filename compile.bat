@@ -1,5 +1,5 @@
 cargo build --release --target wasm32-unknown-unknown
 wasm-bindgen --out-dir "web\js" --target web ..\target\wasm32-unknown-unknown\release\sip_monitor.wasm
 cargo build --release
-cp -r .\web .\build\
+if not exist build mkdir build
 cp ..\target\release\sip_monitor.exe .\build\
\ No newline at end of file
@@ -1,5 +1,15 @@
+use crate::{
+    action_id,
+    Direction,
+    Player,
+    Recorder
+};
 use std::{
     thread,
+    sync::{
+        Arc,
+        Mutex
+    },
     time::Duration,
     io::{
         self,
@@ -8,68 +18,242 @@ use std::{
         prelude::*,
     },
     net::{
+        Shutdown,
         TcpStream,
         SocketAddrV4,
         Ipv4Addr
     },
+    fmt::{
+        self,
+        Display
+    },
 };
 
 pub struct Ami {
-    tcp: TcpStream,
+    link: Link,
+    next_id: u64,
+    recorder: Option<Arc<Recorder>>
     // treat: Option<FuncTreat>
 }
 
+enum Link {
+    /// The socket to write to and the one reader of it, handed to `init_treat` once it starts,
+    /// so no buffered frame is lost between readers
+    Tcp(TcpStream, Option<BufReader<TcpStream>>),
+    /// Actions are dropped, the frames come from the capture
+    Replay(Arc<Mutex<Player>>)
+}
+
 impl Ami {
     pub fn new(connect: AmiConnect) -> IoResult<Self> {
-        let tcp = connect.login()?;
+        let (tcp, read) = connect.login()?;
 
         Ok(Self {
-            tcp,
+            link: Link::Tcp(tcp, Some(read)),
+            // Login already used ActionID 1
+            next_id: 1,
+            recorder: connect.recorder
             // treat: None
         })
     }
 
-    pub fn pjsip_show_aors(&mut self) -> IoResult<()> {
-        println!("Executando comando: PJSIP_ShowAors");
+    /// Plays the next session of a capture instead of a live socket.
+    ///
+    /// The player gives the recorded replies the ActionIDs of the actions sent here, see `Player`.
+    pub fn replay(player: &Arc<Mutex<Player>>) -> IoResult<Self> {
+        {
+            let mut player = player.lock().unwrap();
+            player.connect()?;
+            // Logged in with ActionID 1, like `AmiConnect::login`
+            player.sent("Login", 1);
+        }
+        let read = Player::read(player);
 
-        self.tcp.write(b"Action: PJSIPShowAors\r\n\r\n")?;
-        self.tcp.flush()?;
+        if !read.contains("Message: Authentication accepted") {
+            return Err(io::Error::from(io::ErrorKind::ConnectionRefused))
+        }
 
-        Ok(())
+        Ok(Self {
+            link: Link::Replay(Arc::clone(player)),
+            next_id: 1,
+            recorder: None
+        })
     }
 
-    pub fn pjsip_show_contacts(&mut self) -> IoResult<()> {
-        println!("Executando comando: PJSIP_ShowContacts");
+    /// Reads a frame before `init_treat`, empty once the socket is closed or the reader is gone.
+    fn read(&mut self) -> String {
+        let read = match &mut self.link {
+            Link::Tcp(_, Some(read)) => AmiConnect::read(read),
+            Link::Tcp(_, None) => String::new(),
+            Link::Replay(player) => Player::read(player)
+        };
 
-        self.tcp.write(b"Action: PJSIPShowContacts\r\n\r\n")?;
-        self.tcp.flush()?;
+        if let Some(recorder) = &self.recorder {
+            recorder.record(Direction::In, &read);
+        }
 
-        Ok(())
+        read
     }
 
-    pub fn extension_state(&mut self, sip: &str, ctx: &str) -> IoResult<()> {
-        println!("Executando comando: ExtensionState");
+    /// Sends an action tagged with a fresh `ActionID` and returns it, so the reply can be correlated.
+    pub fn action(&mut self, action: &str, headers: &[(&str, &str)]) -> IoResult<u64> {
+        self.action_frame(action, headers).map(|(id, _)| id)
+    }
 
-        self.tcp.write(format!("Action: ExtensionState\nExten: {sip}\nContext: {ctx}\n\n").as_bytes())?;
-        self.tcp.flush()?;
+    /// Same as `action`, also returning the frame as it was sent.
+    pub fn action_frame(&mut self, action: &str, headers: &[(&str, &str)]) -> IoResult<(u64, String)> {
+        self.next_id += 1;
 
-        Ok(())
+        let mut frame = format!("Action: {action}\r\nActionID: {}\r\n", self.next_id);
+        for (key, value) in headers {
+            frame += &format!("{key}: {value}\r\n");
+        }
+        frame += "\r\n";
+
+        if let Some(recorder) = &self.recorder {
+            recorder.record(Direction::Out, &frame);
+        }
+
+        match &mut self.link {
+            Link::Tcp(tcp, _) => {
+                tcp.write_all(frame.as_bytes())?;
+                tcp.flush()?;
+            },
+            Link::Replay(player) => player.lock().unwrap().sent(action, self.next_id)
+        }
+
+        Ok((self.next_id, frame))
+    }
+
+    pub fn pjsip_show_aors(&mut self) -> IoResult<u64> {
+        elog!("Executando comando: PJSIP_ShowAors");
+
+        self.action("PJSIPShowAors", &[])
+    }
+
+    pub fn core_show_channels(&mut self) -> IoResult<u64> {
+        elog!("Executando comando: CoreShowChannels");
+
+        self.action("CoreShowChannels", &[])
     }
 
-    pub fn init_treat<F>(&self, func: F) -> IoResult<()>
+    pub fn pjsip_show_contacts(&mut self) -> IoResult<u64> {
+        elog!("Executando comando: PJSIP_ShowContacts");
+
+        self.action("PJSIPShowContacts", &[])
+    }
+
+    pub fn pjsip_show_registrations_outbound(&mut self) -> IoResult<u64> {
+        elog!("Executando comando: PJSIPShowRegistrationsOutbound");
+
+        self.action("PJSIPShowRegistrationsOutbound", &[])
+    }
+
+    pub fn pjsip_show_endpoint(&mut self, endpoint: &str) -> IoResult<u64> {
+        elog!("Executando comando: PJSIP_ShowEndpoint");
+
+        self.action("PJSIPShowEndpoint", &[("Endpoint", endpoint)])
+    }
+
+    pub fn parkinglots(&mut self) -> IoResult<u64> {
+        elog!("Executando comando: Parkinglots");
+
+        self.action("Parkinglots", &[])
+    }
+
+    pub fn parked_calls(&mut self) -> IoResult<u64> {
+        elog!("Executando comando: ParkedCalls");
+
+        self.action("ParkedCalls", &[])
+    }
+
+    pub fn confbridge_list_rooms(&mut self) -> IoResult<u64> {
+        elog!("Executando comando: ConfbridgeListRooms");
+
+        self.action("ConfbridgeListRooms", &[])
+    }
+
+    pub fn confbridge_list(&mut self, conference: &str) -> IoResult<u64> {
+        elog!("Executando comando: ConfbridgeList");
+
+        self.action("ConfbridgeList", &[("Conference", conference)])
+    }
+
+    pub fn db_get(&mut self, family: &str, key: &str) -> IoResult<u64> {
+        elog!("Executando comando: DBGet");
+
+        self.action("DBGet", &[("Family", family), ("Key", key)])
+    }
+
+    /// Every key under `family`. Asterisk 18 and later.
+    pub fn db_get_tree(&mut self, family: &str) -> IoResult<u64> {
+        elog!("Executando comando: DBGetTree");
+
+        self.action("DBGetTree", &[("Family", family)])
+    }
+
+    pub fn db_put(&mut self, family: &str, key: &str, val: &str) -> IoResult<u64> {
+        elog!("Executando comando: DBPut");
+
+        self.action("DBPut", &[("Family", family), ("Key", key), ("Val", val)])
+    }
+
+    pub fn db_del(&mut self, family: &str, key: &str) -> IoResult<u64> {
+        elog!("Executando comando: DBDel");
+
+        self.action("DBDel", &[("Family", family), ("Key", key)])
+    }
+
+    pub fn extension_state(&mut self, sip: &str, ctx: &str) -> IoResult<u64> {
+        elog!("Executando comando: ExtensionState");
+
+        self.action("ExtensionState", &[("Exten", sip), ("Context", ctx)])
+    }
+
+    /// Changes the event mask of the running session (`Action: Events`).
+    pub fn events(&mut self, mask: &EventMask) -> IoResult<u64> {
+        elog!("Executando comando: Events");
+
+        self.action("Events", &[("EventMask", &mask.to_string())])
+    }
+
+    /// Starts a `Filter` action. Once a filter is added, Asterisk only sends the events matching one of them.
+    ///
+    /// The replies are read synchronously, so filters must be sent before `init_treat`. Events
+    /// arriving before a reply are dropped, the bootstrap reads the state again anyway.
+    pub fn filter(&mut self) -> Filter<'_> {
+        Filter { ami: self, filters: Vec::new() }
+    }
+
+    pub fn init_treat<F>(&mut self, func: F) -> IoResult<()>
         where F: Fn(String) + Send + Sized + 'static
     {
-        let tcp = self.tcp.try_clone()?;
-        thread::spawn(move || {
-            let mut read = BufReader::new(&tcp);
+        let mut next: Box<dyn FnMut() -> String + Send> = match &mut self.link {
+            Link::Tcp(_, read) => {
+                let mut read = read.take().ok_or_else(|| io::Error::new(io::ErrorKind::Other, "init_treat já iniciado"))?;
+                Box::new(move || AmiConnect::read(&mut read))
+            },
+            Link::Replay(player) => {
+                let player = Arc::clone(player);
+                Box::new(move || Player::read(&player))
+            }
+        };
+        let recorder = self.recorder.clone();
 
+        thread::spawn(move || {
             loop {
-                let value = AmiConnect::read(&mut read);
+                let value = next();
 
                 if value.is_empty() {
-                    println!("Disconnect?");
+                    elog!("Disconnect?");
+                    // Dropping `func` closes the channels it feeds, which tells the receivers
+                    break
                 }
-                
+
+                if let Some(recorder) = &recorder {
+                    recorder.record(Direction::In, &value);
+                }
+
                 func(value);
             }
         });
@@ -78,16 +262,167 @@ impl Ami {
     }
 }
 
+impl Drop for Ami {
+    /// Also ends the `init_treat` thread, which reads from a clone of the socket.
+    fn drop(&mut self) {
+        if let Link::Tcp(tcp, _) = &self.link {
+            let _ = tcp.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+pub struct Filter<'a> {
+    ami: &'a mut Ami,
+    filters: Vec<String>
+}
+
+impl Filter<'_> {
+    /// Adds a raw filter expression, e.g. `Event: ExtensionStatus` or `!Exten: s`.
+    pub fn add<S: Into<String>>(mut self, filter: S) -> Self {
+        self.filters.push(filter.into());
+        self
+    }
+
+    pub fn event(self, name: &str) -> Self {
+        self.add(format!("Event: {name}"))
+    }
+
+    pub fn events(self, names: &[&str]) -> Self {
+        names.iter().fold(self, |filter, name| filter.event(name))
+    }
+
+    pub fn exclude(self, name: &str) -> Self {
+        self.add(format!("!Event: {name}"))
+    }
+
+    pub fn send(self) -> IoResult<()> {
+        elog!("Executando comando: Filter");
+
+        // The capture has only the events it was filtered to, whatever the filters are now
+        if let Link::Replay(_) = self.ami.link {
+            return Ok(())
+        }
+
+        for filter in self.filters {
+            let id = self.ami.action("Filter", &[("Operation", "Add"), ("Filter", &filter)])?;
+
+            let read = loop {
+                let read = self.ami.read();
+
+                if read.is_empty() {
+                    return Err(io::Error::from(io::ErrorKind::UnexpectedEof))
+                }
+                // FullyBooted and any event allowed before the filters
+                if read.starts_with("Response: ") && action_id(&read) == id {
+                    break read
+                }
+            };
+
+            if !read.starts_with("Response: Success") {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, read))
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventClass {
+    System,
+    Call,
+    Log,
+    Verbose,
+    Command,
+    Agent,
+    User,
+    Config,
+    Dtmf,
+    Reporting,
+    Cdr,
+    Dialplan,
+    Originate,
+    Agi,
+    Cc,
+    Aoc,
+    Security,
+    Message
+}
+
+impl EventClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::System => "system",
+            Self::Call => "call",
+            Self::Log => "log",
+            Self::Verbose => "verbose",
+            Self::Command => "command",
+            Self::Agent => "agent",
+            Self::User => "user",
+            Self::Config => "config",
+            Self::Dtmf => "dtmf",
+            Self::Reporting => "reporting",
+            Self::Cdr => "cdr",
+            Self::Dialplan => "dialplan",
+            Self::Originate => "originate",
+            Self::Agi => "agi",
+            Self::Cc => "cc",
+            Self::Aoc => "aoc",
+            Self::Security => "security",
+            Self::Message => "message"
+        }
+    }
+}
+
+/// Value of the `Events:` header sent at login and of the `Events` action.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum EventMask {
+    #[default]
+    On,
+    Off,
+    Classes(Vec<EventClass>)
+}
+
+impl Display for EventMask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::On => write!(f, "on"),
+            Self::Off => write!(f, "off"),
+            Self::Classes(classes) if classes.is_empty() => write!(f, "off"),
+            Self::Classes(classes) => write!(f, "{}", classes.iter().map(EventClass::as_str).collect::<Vec<_>>().join(","))
+        }
+    }
+}
+
+impl<const N: usize> From<[EventClass; N]> for EventMask {
+    fn from(classes: [EventClass; N]) -> Self {
+        Self::Classes(classes.to_vec())
+    }
+}
+
 pub struct AmiConnect {
     user: String,
     pass: String,
     address: Ipv4Addr,
     port: u16,
+    events: EventMask,
+    recorder: Option<Arc<Recorder>>
 }
 
 impl AmiConnect {
     pub fn new(user: String, pass: String, address: Ipv4Addr, port: u16) -> Self {
-        Self { user, pass, address, port }
+        Self { user, pass, address, port, events: EventMask::default(), recorder: None }
+    }
+
+    /// Records the session, login included, to a capture that `Ami::replay` can play.
+    pub fn record(mut self, recorder: Arc<Recorder>) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    pub fn events<E: Into<EventMask>>(mut self, events: E) -> Self {
+        self.events = events.into();
+        self
     }
 
     fn connect(&self) -> IoResult<TcpStream> {
@@ -97,17 +432,27 @@ impl AmiConnect {
         Ok(stream)
     }
 
-    fn login(&self) -> IoResult<TcpStream> {
+    /// The socket and its reader, which may already hold the frames sent after the login reply.
+    fn login(&self) -> IoResult<(TcpStream, BufReader<TcpStream>)> {
         let mut stream = self.connect()?;
-        stream.write(format!("Action: Login\r\nUsername: {}\r\nSecret: {}\r\nActionID: 1\r\n\r\n", self.user, self.pass).as_bytes())?;
+        let login = |secret: &str| format!("Action: Login\r\nUsername: {}\r\nSecret: {secret}\r\nEvents: {}\r\nActionID: 1\r\n\r\n", self.user, self.events);
+        stream.write(login(&self.pass).as_bytes())?;
         stream.flush()?;
 
-        let read: String = Self::read(&mut BufReader::new(&mut stream));
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let read: String = Self::read(&mut reader);
+
+        if let Some(recorder) = &self.recorder {
+            recorder.record(Direction::Connect, &self.address.to_string());
+            // The secret stays out of the capture
+            recorder.record(Direction::Out, &login("********"));
+            recorder.record(Direction::In, &read);
+        }
 
         if read.contains("Message: Authentication accepted") {
-            println!("Autenticação realizada com sucesso!");
+            elog!("Autenticação realizada com sucesso!");
 
-            Ok(stream)
+            Ok((stream, reader))
         } else {
             Err(io::Error::from(io::ErrorKind::ConnectionRefused))
         }
@@ -124,4 +469,52 @@ impl AmiConnect {
 
         buffer.trim().to_owned()
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        net::TcpListener,
+        sync::mpsc
+    };
+
+    /// Logs in with the system class on, so `FullyBooted` comes before the reply to the filter,
+    /// and an event sent along with that reply still reaches `init_treat`.
+    #[test]
+    fn filter_skips_events_before_its_reply() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let server = thread::spawn(move || {
+            let (mut tcp, _) = listener.accept().unwrap();
+            let mut read = BufReader::new(tcp.try_clone().unwrap());
+
+            assert!(AmiConnect::read(&mut read).contains("Events: system"));
+            tcp.write_all(b"Asterisk Call Manager/5.0.1\r\nResponse: Success\r\nActionID: 1\r\nMessage: Authentication accepted\r\n\r\n\
+                Event: FullyBooted\r\nPrivilege: system,all\r\nStatus: Fully Booted\r\n\r\n").unwrap();
+
+            let filter = AmiConnect::read(&mut read);
+            assert!(filter.contains("Filter: Event: ExtensionStatus"));
+            let reply = format!("Event: ExtensionStatus\r\nExten: 2001\r\nStatus: 0\r\nStatusText: Idle\r\n\r\n\
+                Response: Success\r\nActionID: {}\r\nMessage: Added filter\r\n\r\n\
+                Event: ExtensionStatus\r\nExten: 2002\r\nStatus: 1\r\nStatusText: InUse\r\n\r\n", action_id(&filter));
+            tcp.write_all(reply.as_bytes()).unwrap();
+
+            // Keeps the socket open until the client is done
+            AmiConnect::read(&mut read);
+        });
+
+        let connect = AmiConnect::new("admin".to_owned(), "secret".to_owned(), Ipv4Addr::LOCALHOST, port)
+            .events([EventClass::System, EventClass::Call]);
+        let mut ami = Ami::new(connect).unwrap();
+        ami.filter().event("ExtensionStatus").send().unwrap();
+
+        let (send, recv) = mpsc::channel();
+        ami.init_treat(move |val| send.send(val).unwrap()).unwrap();
+
+        assert!(recv.recv_timeout(Duration::from_secs(5)).unwrap().contains("Exten: 2002"));
+
+        drop(ami);
+        server.join().unwrap();
+    }
+}
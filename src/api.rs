@@ -0,0 +1,188 @@
+use crate::*;
+use std::sync::atomic::Ordering::Relaxed;
+
+#[derive(Serialize)]
+struct Extension<'a> {
+    exten: &'a str,
+    state: ExtensionState,
+    #[serde(flatten)]
+    status: &'a SipStatus,
+    contacts: Vec<&'a Registration>,
+    /// DND and forwarding, `None` when none is set
+    features: Option<&'a CallFeatures>
+}
+
+#[derive(Serialize)]
+struct Pbx<'a> {
+    pbx: &'a str,
+    connected: bool,
+    reconnects: u64,
+    frames: u64,
+    bootstrap_seconds: f64,
+    extensions: usize,
+    active_calls: usize
+}
+
+#[derive(Serialize)]
+struct TrunkUsage<'a> {
+    #[serde(flatten)]
+    trunk: &'a Trunk,
+    channels: usize
+}
+
+#[derive(Serialize)]
+struct Health<'a> {
+    status: &'a str,
+    /// `None` when the bridge has no AMI session of its own
+    ami: Option<bool>
+}
+
+#[derive(Serialize)]
+struct Call<'a> {
+    linkedid: &'a str,
+    /// Unix timestamp of the oldest channel
+    started: u64,
+    channels: Vec<&'a Channel>
+}
+
+/// Serves the `/api/...` routes from the monitor state.
+pub fn api(monitor: &Monitor, req: &Request) -> Response {
+    if req.method != "GET" {
+        return Response::new(405, "text/plain; charset=utf-8", "Method Not Allowed")
+    }
+
+    match req.path.trim_end_matches('/').split('/').skip(2).collect::<Vec<_>>()[..] {
+        ["extensions"] => {
+            let (data, regs, features) = (monitor.data.lock().unwrap(), monitor.regs.lock().unwrap(), monitor.features.lock().unwrap());
+
+            Response::json(&data.iter().map(|(k, v)| extension(k, v, &regs, &features)).collect::<Vec<_>>())
+        },
+        ["extensions", exten] => {
+            let (data, regs, features) = (monitor.data.lock().unwrap(), monitor.regs.lock().unwrap(), monitor.features.lock().unwrap());
+
+            data.get_key_value(exten).map_or_else(Response::not_found, |(k, v)| Response::json(&extension(k, v, &regs, &features)))
+        },
+        ["pbx"] => {
+            let stats = &monitor.stats;
+
+            Response::json(&Pbx {
+                pbx: &monitor.pbx,
+                connected: stats.connected.load(Relaxed),
+                reconnects: stats.reconnects.load(Relaxed),
+                frames: stats.frames.load(Relaxed),
+                bootstrap_seconds: stats.bootstrap_ms.load(Relaxed) as f64 / 1000.,
+                extensions: monitor.data.lock().unwrap().len(),
+                active_calls: active_calls(&monitor.channels.lock().unwrap())
+            })
+        },
+        ["calls"] => {
+            let channels = monitor.channels.lock().unwrap();
+            let mut calls = BTreeMap::<&str, Call>::new();
+
+            for channel in channels.values() {
+                let call = calls.entry(&channel.linkedid).or_insert_with(|| Call { linkedid: &channel.linkedid, started: channel.created, channels: Vec::new() });
+
+                call.started = call.started.min(channel.created);
+                call.channels.push(channel);
+            }
+
+            Response::json(&calls.into_values().collect::<Vec<_>>())
+        },
+        ["trunks"] => {
+            let (trunks, channels) = (monitor.trunks.lock().unwrap(), monitor.channels.lock().unwrap());
+
+            Response::json(&trunks.values().map(|trunk| TrunkUsage { trunk, channels: trunk.usage(&channels) }).collect::<Vec<_>>())
+        },
+        ["parking"] => Response::json(&monitor.parking.lock().unwrap().values().collect::<Vec<_>>()),
+        ["conferences"] => Response::json(&monitor.conferences.lock().unwrap().values().collect::<Vec<_>>()),
+        ["history"] => {
+            let Ok(since) = req.param("since").map_or(Ok(0), str::parse::<u64>) else {
+                return Response::new(400, "text/plain; charset=utf-8", "Invalid since")
+            };
+            let history = monitor.history.lock().unwrap();
+
+            Response::json(&history.iter()
+                .filter(|x| x.at >= since && req.param("exten").map_or(true, |exten| x.exten == exten))
+                .collect::<Vec<_>>())
+        },
+        _ => Response::not_found()
+    }
+}
+
+/// `/health`: up as long as the bridge answers, degraded while its AMI session is down.
+pub fn health(monitor: Option<&Monitor>) -> Response {
+    let ami = monitor.map(|x| x.stats.connected.load(Relaxed));
+    let res = Response::json(&Health { status: if ami == Some(false) { "degraded" } else { "ok" }, ami });
+
+    Response { status: if ami == Some(false) { 503 } else { 200 }, ..res }
+}
+
+fn extension<'a>(exten: &'a str, status: &'a SipStatus, regs: &'a BTreeMap<String, Registration>, features: &'a BTreeMap<String, CallFeatures>) -> Extension<'a> {
+    Extension {
+        exten,
+        state: status.state(),
+        status,
+        contacts: regs.values().filter(|x| x.aor == exten).collect(),
+        features: features.get(exten).filter(|x| !x.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get(monitor: &Monitor, target: &str) -> (u16, serde_json::Value) {
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let req = Request {
+            method: "GET".to_owned(),
+            path: path.to_owned(),
+            query: query.split('&').filter_map(|x| x.split_once('=')).map(|(k, v)| (k.to_owned(), v.to_owned())).collect(),
+            ..Default::default()
+        };
+        let res = api(monitor, &req);
+
+        (res.status, serde_json::from_slice(&res.body).unwrap_or_default())
+    }
+
+    #[test]
+    fn routes() {
+        let monitor = capture::replayed();
+
+        let (status, extensions) = get(&monitor, "/api/extensions");
+        assert_eq!(status, 200);
+        assert_eq!(extensions.as_array().unwrap().iter().map(|x| x["exten"].as_str().unwrap()).collect::<Vec<_>>(), ["2001", "2002"]);
+
+        let (status, extension) = get(&monitor, "/api/extensions/2001/");
+        assert_eq!((status, &extension["state"], &extension["features"]["dnd"]), (200, &"idle".into(), &true.into()));
+        assert_eq!(extension["contacts"].as_array().unwrap().len(), 1);
+
+        let (_, extension) = get(&monitor, "/api/extensions/2002");
+        assert_eq!((&extension["state"], &extension["features"]), (&"in_use".into(), &serde_json::Value::Null));
+
+        let (status, pbx) = get(&monitor, "/api/pbx");
+        assert_eq!((status, &pbx["pbx"], &pbx["connected"], &pbx["active_calls"]), (200, &"10.0.0.1".into(), &false.into(), &1.into()));
+
+        let (_, calls) = get(&monitor, "/api/calls");
+        assert_eq!(calls.as_array().unwrap().len(), 1);
+
+        let (_, history) = get(&monitor, "/api/history?exten=2002");
+        assert_eq!(history.as_array().unwrap().len(), 1);
+        assert_eq!(get(&monitor, "/api/history?exten=2001").1, serde_json::json!([]));
+    }
+
+    #[test]
+    fn status_codes() {
+        let monitor = capture::replayed();
+
+        assert_eq!(get(&monitor, "/api/extensions/9999").0, 404);
+        assert_eq!(get(&monitor, "/api/nothing").0, 404);
+        assert_eq!(get(&monitor, "/api/history?since=yesterday").0, 400);
+
+        let post = Request { method: "POST".to_owned(), path: "/api/extensions".to_owned(), ..Default::default() };
+        assert_eq!(api(&monitor, &post).status, 405);
+
+        assert_eq!(health(None).status, 200);
+        let res = health(Some(&monitor));
+        assert_eq!((res.status, String::from_utf8(res.body).unwrap()), (503, r#"{"status":"degraded","ami":false}"#.to_owned()));
+    }
+}
@@ -0,0 +1,89 @@
+use crate::*;
+use std::{
+    fs,
+    path::{
+        Component,
+        Path
+    }
+};
+
+/// A file of `web/`, embedded by `build.rs`.
+struct Asset {
+    /// `/index.html`, `/js/main.js`, ...
+    path: &'static str,
+    etag: &'static str,
+    body: &'static [u8],
+    gzip: Option<&'static [u8]>
+}
+
+static ASSETS: &[Asset] = include!(concat!(env!("OUT_DIR"), "/assets.rs"));
+
+/// Serves the browser client, `/` being `index.html`.
+///
+/// The files are the ones embedded at build time, unless `web_dir` points to a directory
+/// to read them from on every request, for development.
+pub fn asset(req: &Request, web_dir: Option<&str>) -> Response {
+    if req.method != "GET" {
+        return Response::new(405, "text/plain; charset=utf-8", "Method Not Allowed")
+    }
+
+    let path = match &req.path[..] {
+        "/" => "/index.html",
+        path => path
+    };
+
+    if let Some(dir) = web_dir {
+        return from_disk(dir, path)
+    }
+
+    let Some(asset) = ASSETS.iter().find(|x| x.path == path) else { return Response::not_found() };
+
+    // The names carry no hash, so browsers check the ETag before using their copy
+    if req.headers.get("if-none-match").map_or(false, |x| x.split(',').any(|x| x.trim() == asset.etag)) {
+        return Response::new(304, mime(path), "")
+            .header("Cache-Control", "no-cache")
+            .header("ETag", asset.etag)
+    }
+
+    let accepts_gzip = req.headers.get("accept-encoding").map_or(false, |x| x.split(',').any(|x| x.trim().starts_with("gzip")));
+    let gzip = asset.gzip.filter(|_| accepts_gzip);
+    let res = Response::new(200, mime(path), gzip.unwrap_or(asset.body))
+        .header("Cache-Control", "no-cache")
+        .header("ETag", asset.etag)
+        .header("Vary", "Accept-Encoding");
+
+    match gzip {
+        Some(_) => res.header("Content-Encoding", "gzip"),
+        None => res
+    }
+}
+
+fn from_disk(dir: &str, path: &str) -> Response {
+    let path = path.trim_start_matches('/');
+
+    // Only plain names, nothing like `..` or `C:` that could leave the directory
+    if !Path::new(path).components().all(|x| matches!(x, Component::Normal(_))) {
+        return Response::not_found()
+    }
+
+    match fs::read(Path::new(dir).join(path)) {
+        Ok(body) => Response::new(200, mime(path), body).header("Cache-Control", "no-store"),
+        Err(_) => Response::not_found()
+    }
+}
+
+fn mime(path: &str) -> &'static str {
+    match path.rsplit_once('.').map(|(_, ext)| ext) {
+        Some("html") => "text/html; charset=utf-8",
+        Some("js") => "text/javascript; charset=utf-8",
+        Some("css") => "text/css; charset=utf-8",
+        Some("wasm") => "application/wasm",
+        Some("json" | "map") => "application/json",
+        Some("ts") => "text/plain; charset=utf-8",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("ico") => "image/x-icon",
+        Some("woff2") => "font/woff2",
+        _ => "application/octet-stream"
+    }
+}
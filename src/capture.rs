@@ -0,0 +1,300 @@
+use crate::*;
+use std::{
+    collections::{
+        HashMap,
+        HashSet
+    },
+    fs::{self, File},
+    io::BufWriter,
+    path::Path,
+    time::Instant
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    /// A new session, `frame` is the PBX address
+    Connect,
+    /// Frame read from the PBX
+    In,
+    /// Action written to the PBX
+    Out
+}
+
+/// One line of a capture file.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Captured {
+    /// Milliseconds since the capture started
+    pub ms: u64,
+    pub dir: Direction,
+    pub frame: String
+}
+
+/// Appends every frame of the sessions it's given to a capture file, one JSON line each.
+pub struct Recorder {
+    started: Instant,
+    file: Mutex<BufWriter<File>>
+}
+
+impl Recorder {
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Arc<Self>> {
+        Ok(Arc::new(Self {
+            started: Instant::now(),
+            file: Mutex::new(BufWriter::new(File::create(path)?))
+        }))
+    }
+
+    /// Failing to record is reported but never stops the session.
+    pub fn record(&self, dir: Direction, frame: &str) {
+        let line = Captured { ms: self.started.elapsed().as_millis() as u64, dir, frame: frame.to_owned() };
+        let mut file = self.file.lock().unwrap();
+
+        if let Err(e) = serde_json::to_writer(&mut *file, &line).map_err(io::Error::from).and_then(|_| writeln!(file)).and_then(|_| file.flush()) {
+            elog!("Falha ao gravar captura: {e}");
+        }
+    }
+}
+
+/// How long a recorded reply waits for the session to send its action before it's played as recorded.
+const REPLY_GRACE: Duration = Duration::from_secs(2);
+
+/// Feeds the frames of a capture file back, keeping their original timing divided by `speed`.
+///
+/// The session doesn't have to send the same actions as the recorded one: each action it sends
+/// takes the next recorded one of the same name, and the replies are given its ActionID.
+pub struct Player {
+    /// Address of the first recorded session
+    pub pbx: String,
+    frames: VecDeque<Captured>,
+    started: Instant,
+    /// `0` plays as fast as possible
+    speed: f64,
+    /// ActionID sent by the session for each recorded one
+    ids: HashMap<u64, u64>,
+    /// Actions sent by the session and not recorded yet, by name
+    sent: HashMap<String, VecDeque<u64>>,
+    /// Recorded actions the session hasn't sent yet, by name
+    recorded: HashMap<String, VecDeque<u64>>,
+    /// Recorded `Filter` actions, a replay has only the events the capture was filtered to
+    filters: HashSet<u64>,
+    /// Since when the next reply waits for its action
+    held: Option<Instant>
+}
+
+enum Next {
+    Frame(String),
+    Wait(Duration),
+    End
+}
+
+impl Player {
+    pub fn open<P: AsRef<Path>>(path: P, speed: f64) -> Result<Arc<Mutex<Self>>> {
+        let frames = fs::read_to_string(path.as_ref())?
+            .lines()
+            .filter(|x| !x.trim().is_empty())
+            .map(serde_json::from_str::<Captured>)
+            .collect::<serde_json::Result<VecDeque<_>>>()?;
+        let pbx = frames.iter()
+            .find(|x| x.dir == Direction::Connect)
+            .map_or_else(|| path.as_ref().display().to_string(), |x| x.frame.clone());
+
+        Ok(Arc::new(Mutex::new(Self {
+            pbx,
+            frames,
+            started: Instant::now(),
+            speed,
+            ids: HashMap::new(),
+            sent: HashMap::new(),
+            recorded: HashMap::new(),
+            filters: HashSet::new(),
+            held: None
+        })))
+    }
+
+    /// Skips to the next recorded session, failing with `UnexpectedEof` once there are none left.
+    pub fn connect(&mut self) -> Result<()> {
+        self.ids.clear();
+        self.sent.clear();
+        self.recorded.clear();
+        self.filters.clear();
+        self.held = None;
+
+        while let Some(frame) = self.frames.pop_front() {
+            if frame.dir == Direction::Connect {
+                return Ok(())
+            }
+        }
+
+        Err(io::Error::new(io::ErrorKind::UnexpectedEof, "fim da captura"))
+    }
+
+    /// Pairs an action of the session with the next recorded one of the same name.
+    pub fn sent(&mut self, action: &str, id: u64) {
+        match self.recorded.get_mut(action).and_then(VecDeque::pop_front) {
+            Some(recorded) => {
+                self.ids.insert(recorded, id);
+            },
+            None => self.sent.entry(action.to_owned()).or_default().push_back(id)
+        }
+    }
+
+    /// Next frame read from the PBX, once it's due. Empty at the end of the session, like a closed socket.
+    ///
+    /// Sleeps without holding `player`, so the session can send its actions meanwhile.
+    pub fn read(player: &Mutex<Self>) -> String {
+        loop {
+            match player.lock().unwrap().next() {
+                Next::Frame(frame) => return frame,
+                Next::Wait(wait) => thread::sleep(wait),
+                Next::End => return String::new()
+            }
+        }
+    }
+
+    fn next(&mut self) -> Next {
+        while let Some(&Captured { ms, dir, .. }) = self.frames.front() {
+            match dir {
+                Direction::Connect => break,
+                Direction::Out => {
+                    let frame = self.frames.pop_front().unwrap().frame;
+                    let v = get_map(&frame);
+                    let (Some(action), Some(id)) = (v.get("Action"), v.get("ActionID").and_then(|x| x.parse().ok())) else { continue };
+
+                    if *action == "Filter" {
+                        self.filters.insert(id);
+                        continue
+                    }
+
+                    match self.sent.get_mut(*action).and_then(VecDeque::pop_front) {
+                        Some(live) => {
+                            self.ids.insert(id, live);
+                        },
+                        None => self.recorded.entry(action.to_string()).or_default().push_back(id)
+                    }
+                },
+                Direction::In => {
+                    if self.speed > 0. {
+                        let due = Duration::from_millis(ms).div_f64(self.speed).saturating_sub(self.started.elapsed());
+
+                        if !due.is_zero() {
+                            return Next::Wait(due)
+                        }
+                    }
+
+                    let id = action_id(&self.frames[0].frame);
+
+                    if self.filters.contains(&id) {
+                        self.frames.pop_front();
+                        continue
+                    }
+
+                    // The session may be slower than the recorded one
+                    if self.recorded.values().any(|x| x.contains(&id)) {
+                        let held = *self.held.get_or_insert_with(Instant::now);
+
+                        if held.elapsed() < REPLY_GRACE {
+                            return Next::Wait(Duration::from_millis(10))
+                        }
+                        // Not sent by this version, played as recorded
+                        for ids in self.recorded.values_mut() {
+                            ids.retain(|x| *x != id);
+                        }
+                    }
+                    self.held = None;
+
+                    let frame = self.frames.pop_front().unwrap().frame;
+
+                    return Next::Frame(match self.ids.get(&id) {
+                        Some(live) => frame.split("\r\n")
+                            .map(|x| if x.starts_with("ActionID: ") { format!("ActionID: {live}") } else { x.to_owned() })
+                            .collect::<Vec<_>>()
+                            .join("\r\n"),
+                        None => frame
+                    })
+                }
+            }
+        }
+
+        Next::End
+    }
+}
+
+/// The capture of `tests/fixtures` played to the end, for the tests of what's built on a monitor.
+#[cfg(test)]
+pub(crate) fn replayed() -> Arc<Monitor> {
+    let file = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/replay.jsonl").to_owned();
+    let monitor = Monitor::replay(&ReplaySettings { file, speed: 0. }, Vec::new(), BOOTSTRAP_WINDOW).unwrap();
+
+    // Closed once the capture is over
+    for _ in monitor.subscribe() {}
+
+    monitor
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two extensions, one registered, on a call and in DND: the bootstrap, then a call from the
+    /// other one and the first hanging up.
+    const CAPTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/replay.jsonl");
+
+    /// Plays `file` to the end and checks the state it leaves.
+    fn replay(file: &str, speed: f64) {
+        let monitor = Monitor::replay(&ReplaySettings { file: file.to_owned(), speed }, Vec::new(), BOOTSTRAP_WINDOW).unwrap();
+        let events = monitor.subscribe();
+
+        assert_eq!(monitor.pbx, "10.0.0.1");
+        assert_eq!(monitor.data.lock().unwrap().values().map(|x| x.status).collect::<Vec<_>>(), [0, 0]);
+
+        // Closed once the capture is over
+        let changes = events.iter()
+            .filter_map(|x| match x { MonitorEvent::Status(change) => Some((change.exten, change.new.status_text)), _ => None })
+            .collect::<Vec<_>>();
+        assert_eq!(changes, [("2002".to_owned(), "InUse".to_owned())]);
+
+        let data = monitor.data.lock().unwrap();
+        assert_eq!((data["2001"].state(), data["2002"].state()), (ExtensionState::Idle, ExtensionState::InUse));
+
+        let regs = monitor.regs.lock().unwrap();
+        let reg = &regs["sip:2001@10.0.0.10:5060"];
+        assert_eq!((&reg.aor[..], reg.status, reg.rtt), ("2001", Reachability::Reachable, Some(12_000)));
+
+        let channels = monitor.channels.lock().unwrap();
+        assert_eq!(channels.values().map(|x| (x.endpoint(), &x.state[..])).collect::<Vec<_>>(), [("2002", "Ring")]);
+
+        assert!(monitor.features.lock().unwrap()["2001"].dnd);
+        assert_eq!(monitor.parking.lock().unwrap()["default"].free(), Some(20));
+    }
+
+    #[test]
+    fn replays_a_recorded_session() {
+        replay(CAPTURE, 1.);
+    }
+
+    /// A capture recorded by another version, which numbered its actions differently.
+    #[test]
+    fn replies_follow_the_actions_not_their_numbers() {
+        let capture = fs::read_to_string(CAPTURE).unwrap()
+            .lines()
+            .map(|line| {
+                let mut captured = serde_json::from_str::<Captured>(line).unwrap();
+                captured.frame = captured.frame.split("\r\n")
+                    .map(|x| match x.strip_prefix("ActionID: ").and_then(|x| x.parse::<u64>().ok()) {
+                        Some(id) if id > 1 => format!("ActionID: {}", id + 100),
+                        _ => x.to_owned()
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\r\n");
+
+                serde_json::to_string(&captured).unwrap()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let path = std::env::temp_dir().join(format!("sip_monitor-replay-{}.jsonl", std::process::id()));
+        fs::write(&path, capture).unwrap();
+
+        replay(&path.display().to_string(), 0.);
+        let _ = fs::remove_file(path);
+    }
+}
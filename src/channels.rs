@@ -0,0 +1,86 @@
+use crate::*;
+
+/// Events the channel tracking consumes (class `call`).
+pub const CHANNEL_EVENTS: &[&str] = &["Newchannel", "Newstate", "Hangup"];
+
+/// Live channels keyed by `Uniqueid`.
+pub type Channels = Arc<Mutex<BTreeMap<String, Channel>>>;
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct Channel {
+    pub channel: String,
+    pub uniqueid: String,
+    pub linkedid: String,
+    pub state: String,
+    pub caller: String,
+    pub connected: String,
+    pub exten: String,
+    /// Unix timestamp at which the channel was seen first
+    pub created: u64
+}
+
+impl Channel {
+    fn from_map(v: &HashMap<&str, &str>) -> Self {
+        let get = |key| v.get(key).unwrap_or(&"").to_string();
+
+        Self {
+            channel: get("Channel"),
+            uniqueid: get("Uniqueid"),
+            linkedid: get("Linkedid"),
+            state: get("ChannelStateDesc"),
+            caller: get("CallerIDNum"),
+            connected: get("ConnectedLineNum"),
+            exten: get("Exten"),
+            // CoreShowChannel reports how long the channel has been up
+            created: unix_now().saturating_sub(v.get("Duration").map(|x| parse_duration(x)).unwrap_or_default())
+        }
+    }
+
+    /// Endpoint of the channel, `100` for `PJSIP/100-0000002a`.
+    pub fn endpoint(&self) -> &str {
+        let name = self.channel.split_once('/').map_or(&self.channel[..], |x| x.1);
+
+        name.rsplit_once('-').map_or(name, |x| x.0)
+    }
+}
+
+/// Number of calls, counting the channels of a call (same `Linkedid`) once.
+pub fn active_calls(channels: &BTreeMap<String, Channel>) -> usize {
+    channels.values().map(|x| &x.linkedid).collect::<HashSet<_>>().len()
+}
+
+fn parse_duration(val: &str) -> u64 {
+    val.split(':').fold(0, |acc, x| acc * 60 + x.parse::<u64>().unwrap_or_default())
+}
+
+/// Tracks channels from `Newchannel`/`Newstate`/`Hangup` and the `CoreShowChannel` list.
+pub fn treat_channels(val: &str, data: &Mutex<BTreeMap<String, Channel>>) {
+    match val.as_bytes() {
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'C', b'o', b'r', b'e', b'S', b'h', b'o', b'w', b'C', b'h', b'a', b'n', b'n', b'e', b'l', b's', ..] => (),
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'N', b'e', b'w', b'c', b'h', b'a', b'n', b'n', b'e', b'l', ..] |
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'C', b'o', b'r', b'e', b'S', b'h', b'o', b'w', b'C', b'h', b'a', b'n', b'n', b'e', b'l', ..] => {
+            let channel = Channel::from_map(&get_map(val));
+
+            if !channel.uniqueid.is_empty() {
+                data.lock().unwrap().insert(channel.uniqueid.clone(), channel);
+            }
+        },
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'N', b'e', b'w', b's', b't', b'a', b't', b'e', ..] => {
+            let v = get_map(val);
+            let Some(id) = v.get("Uniqueid") else { return };
+
+            if let Some(channel) = data.lock().unwrap().get_mut(*id) {
+                channel.state = v.get("ChannelStateDesc").unwrap_or(&"").to_string();
+                channel.connected = v.get("ConnectedLineNum").unwrap_or(&"").to_string();
+            }
+        },
+        // Not HangupRequest nor HangupHandler*
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'H', b'a', b'n', b'g', b'u', b'p', b'\r', ..] => {
+            let v = get_map(val);
+            let Some(id) = v.get("Uniqueid") else { return };
+
+            data.lock().unwrap().remove(*id);
+        },
+        _ => ()
+    }
+}
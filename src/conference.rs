@@ -0,0 +1,140 @@
+use crate::*;
+
+/// Events the conference panel consumes (class `call`).
+pub const CONFERENCE_EVENTS: &[&str] = &[
+    "ConfbridgeStart", "ConfbridgeEnd", "ConfbridgeJoin", "ConfbridgeLeave", "ConfbridgeTalking",
+    "ConfbridgeMute", "ConfbridgeUnmute", "ConfbridgeLock", "ConfbridgeUnlock"
+];
+
+/// Active ConfBridge rooms keyed by name.
+pub type Conferences = Arc<Mutex<BTreeMap<String, Conference>>>;
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct Conference {
+    pub name: String,
+    pub locked: bool,
+    /// Participants keyed by channel
+    pub participants: BTreeMap<String, Participant>
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct Participant {
+    pub channel: String,
+    pub caller: String,
+    pub caller_name: String,
+    pub admin: bool,
+    pub marked: bool,
+    pub muted: bool,
+    pub talking: bool,
+    /// Unix timestamp at which the participant joined
+    pub joined: u64
+}
+
+/// Updates `conferences` from the `ConfbridgeListRooms` and `ConfbridgeList` replies and the
+/// ConfBridge events; other frames are ignored.
+pub fn treat_conferences(val: &str, conferences: &Conferences) {
+    let [b'E', b'v', b'e', b'n', b't', b':', b' ', b'C', b'o', b'n', b'f', b'b', b'r', b'i', b'd', b'g', b'e', event @ ..] = val.as_bytes() else { return };
+    let v = get_map(val);
+    let Some(name) = v.get("Conference") else { return };
+    let yes = |key: &str| v.get(key).map_or(false, |x| x.eq_ignore_ascii_case("yes"));
+    let mut conferences = conferences.lock().unwrap();
+
+    match event {
+        [b'E', b'n', b'd', b'\r', ..] => {
+            conferences.remove(*name);
+            return
+        },
+        [b'L', b'i', b's', b't', b'R', b'o', b'o', b'm', b's', b'C', b'o', b'm', b'p', b'l', b'e', b't', b'e', ..] |
+        [b'L', b'i', b's', b't', b'C', b'o', b'm', b'p', b'l', b'e', b't', b'e', ..] => return,
+        _ => ()
+    }
+
+    let conference = conferences.entry(name.to_string()).or_default();
+    conference.name = name.to_string();
+
+    match event {
+        [b'L', b'i', b's', b't', b'R', b'o', b'o', b'm', b's', b'\r', ..] => conference.locked = yes("Locked"),
+        [b'L', b'o', b'c', b'k', b'\r', ..] => conference.locked = true,
+        [b'U', b'n', b'l', b'o', b'c', b'k', b'\r', ..] => conference.locked = false,
+        [b'J', b'o', b'i', b'n', b'\r', ..] | [b'L', b'i', b's', b't', b'\r', ..] => {
+            let Some(channel) = v.get("Channel") else { return };
+            let get = |key: &str| v.get(key).unwrap_or(&"").to_string();
+            // Only the list tells since when, a join is now
+            let answered = v.get("AnsweredTime").and_then(|x| x.parse::<u64>().ok()).unwrap_or_default();
+
+            conference.participants.insert(channel.to_string(), Participant {
+                channel: channel.to_string(),
+                caller: get("CallerIDNum"),
+                caller_name: get("CallerIDName"),
+                admin: yes("Admin"),
+                marked: yes("MarkedUser"),
+                muted: yes("Muted"),
+                talking: yes("Talking"),
+                joined: unix_now().saturating_sub(answered)
+            });
+        },
+        [b'L', b'e', b'a', b'v', b'e', b'\r', ..] => {
+            if let Some(channel) = v.get("Channel") {
+                conference.participants.remove(*channel);
+            }
+        },
+        [b'T', b'a', b'l', b'k', b'i', b'n', b'g', b'\r', ..] => {
+            let Some(participant) = v.get("Channel").and_then(|x| conference.participants.get_mut(*x)) else { return };
+            participant.talking = v.get("TalkingStatus") == Some(&"on");
+        },
+        [b'M', b'u', b't', b'e', b'\r', ..] | [b'U', b'n', b'm', b'u', b't', b'e', b'\r', ..] => {
+            let Some(participant) = v.get("Channel").and_then(|x| conference.participants.get_mut(*x)) else { return };
+            participant.muted = event[0] == b'M';
+        },
+        // ConfbridgeStart
+        _ => ()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follows_a_conference() {
+        let conferences = Conferences::default();
+
+        treat_conferences("Event: ConfbridgeListRooms\r\nConference: 8000\r\nParties: 1\r\nLocked: No\r\n", &conferences);
+        treat_conferences("Event: ConfbridgeList\r\nConference: 8000\r\nChannel: PJSIP/2003-00000001\r\nCallerIDNum: 2003\r\nCallerIDName: Maria\r\nAdmin: Yes\r\nMarkedUser: No\r\nMuted: No\r\nTalking: No\r\nAnsweredTime: 60\r\n", &conferences);
+        treat_conferences("Event: ConfbridgeListComplete\r\nConference: 8000\r\n", &conferences);
+        treat_conferences("Event: ConfbridgeJoin\r\nConference: 8000\r\nChannel: PJSIP/2004-00000002\r\nCallerIDNum: 2004\r\n", &conferences);
+        treat_conferences("Event: ConfbridgeTalking\r\nConference: 8000\r\nChannel: PJSIP/2004-00000002\r\nTalkingStatus: on\r\n", &conferences);
+        treat_conferences("Event: ConfbridgeMute\r\nConference: 8000\r\nChannel: PJSIP/2003-00000001\r\n", &conferences);
+        treat_conferences("Event: ConfbridgeLock\r\nConference: 8000\r\n", &conferences);
+
+        {
+            let conferences = conferences.lock().unwrap();
+            let conference = &conferences["8000"];
+            let maria = &conference.participants["PJSIP/2003-00000001"];
+            let now = unix_now();
+
+            assert!(conference.locked);
+            assert_eq!(conference.participants.len(), 2);
+            assert!(maria.admin && maria.muted && !maria.talking);
+            assert!((now - 61..=now - 60).contains(&maria.joined));
+            assert!(conference.participants["PJSIP/2004-00000002"].talking);
+        }
+
+        treat_conferences("Event: ConfbridgeLeave\r\nConference: 8000\r\nChannel: PJSIP/2004-00000002\r\n", &conferences);
+        treat_conferences("Event: ConfbridgeUnmute\r\nConference: 8000\r\nChannel: PJSIP/2003-00000001\r\n", &conferences);
+        treat_conferences("Event: ConfbridgeUnlock\r\nConference: 8000\r\n", &conferences);
+
+        {
+            let conferences = conferences.lock().unwrap();
+            let conference = &conferences["8000"];
+
+            assert!(!conference.locked);
+            assert_eq!(conference.participants.len(), 1);
+            assert!(!conference.participants["PJSIP/2003-00000001"].muted);
+        }
+
+        treat_conferences("Event: ConfbridgeEnd\r\nConference: 8000\r\n", &conferences);
+
+        assert!(conferences.lock().unwrap().is_empty());
+    }
+}
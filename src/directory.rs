@@ -0,0 +1,262 @@
+use crate::*;
+#[cfg(not(target_arch = "wasm32"))]
+use std::{
+    fs,
+    path::Path
+};
+
+/// Directory entries by extension.
+pub type Directory = Arc<Mutex<BTreeMap<String, Contact>>>;
+
+/// Field the grid groups the tiles by.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupBy {
+    #[default]
+    None,
+    Department,
+    Location,
+    Tag
+}
+
+impl GroupBy {
+    pub const ALL: [Self; 4] = [Self::None, Self::Department, Self::Location, Self::Tag];
+}
+
+impl Display for GroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "None"),
+            Self::Department => write!(f, "Department"),
+            Self::Location => write!(f, "Location"),
+            Self::Tag => write!(f, "Tag")
+        }
+    }
+}
+
+impl Contact {
+    /// Groups the contact belongs to, `-` when the field is empty. A tag is a group of its own.
+    pub fn groups(&self, by: GroupBy) -> Vec<&str> {
+        let groups = match by {
+            GroupBy::None => return vec![""],
+            GroupBy::Department => self.department.as_deref().into_iter().collect(),
+            GroupBy::Location => self.location.as_deref().into_iter().collect(),
+            GroupBy::Tag => self.tags.iter().map(|x| &x[..]).collect::<Vec<_>>()
+        };
+
+        if groups.is_empty() { vec!["-"] } else { groups }
+    }
+
+    /// Whether any field contains `filter`, which must be lowercase.
+    pub fn matches(&self, filter: &str) -> bool {
+        [Some(&self.name), self.display_name.as_ref(), self.department.as_ref(), self.location.as_ref()]
+            .into_iter()
+            .flatten()
+            .chain(&self.tags)
+            .any(|x| x.to_lowercase().contains(filter))
+    }
+
+    /// Fills what `other` knows and `self` doesn't.
+    fn merge(&mut self, other: Contact) {
+        if self.contact.is_empty() {
+            self.contact = other.contact;
+        }
+        self.display_name = self.display_name.take().or(other.display_name);
+        self.department = self.department.take().or(other.department);
+        self.location = self.location.take().or(other.location);
+
+        if self.tags.is_empty() {
+            self.tags = other.tags;
+        }
+    }
+}
+
+/// Loads a directory file: LDIF when it ends in `.ldif`, CSV otherwise.
+///
+/// The CSV needs a header naming its columns: `exten`, `name`, `department`, `location`
+/// and `tags`, the tags separated by `;`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_directory<P: AsRef<Path>>(path: P) -> Result<Vec<Contact>> {
+    let text = fs::read_to_string(path.as_ref())?;
+
+    if path.as_ref().extension().map_or(false, |x| x.eq_ignore_ascii_case("ldif")) {
+        Ok(parse_ldif(&text))
+    } else {
+        parse_csv(&text)
+    }
+}
+
+/// Merges `entries` into `directory`, their fields taking precedence over the ones already known.
+pub fn merge_directory(directory: &Directory, entries: Vec<Contact>) {
+    let mut directory = directory.lock().unwrap();
+
+    for mut entry in entries {
+        if let Some(old) = directory.remove(&entry.name) {
+            entry.merge(old);
+        }
+
+        directory.insert(entry.name.clone(), entry);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn parse_csv(text: &str) -> Result<Vec<Contact>> {
+    let mut lines = text.lines().filter(|x| !x.trim().is_empty());
+    let header = lines.next().map(split_csv).unwrap_or_default();
+    let column = |names: &[&str]| header.iter().position(|x| names.iter().any(|name| x.trim().eq_ignore_ascii_case(name)));
+    let exten = column(&["exten", "extension"]).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "directory without an exten column"))?;
+    let (name, department, location, tags) = (column(&["name", "display_name"]), column(&["department"]), column(&["location"]), column(&["tags"]));
+
+    Ok(lines.map(split_csv).filter_map(|row| {
+        let field = |idx: Option<usize>| idx.and_then(|x| row.get(x)).map(|x| x.trim().to_owned()).filter(|x| !x.is_empty());
+
+        Some(Contact {
+            name: field(Some(exten))?,
+            display_name: field(name),
+            department: field(department),
+            location: field(location),
+            tags: field(tags).map(|x| x.split(';').map(|x| x.trim().to_owned()).filter(|x| !x.is_empty()).collect()).unwrap_or_default(),
+            ..Default::default()
+        })
+    }).collect())
+}
+
+/// Splits a CSV line, `"` quoting fields with commas or quotes.
+#[cfg(not(target_arch = "wasm32"))]
+fn split_csv(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            },
+            ('"', _) => quoted = !quoted,
+            (',', false) => fields.push(String::new()),
+            (c, _) => fields.last_mut().unwrap().push(c)
+        }
+    }
+
+    fields
+}
+
+/// Reads the entries with a `telephoneNumber` (or `ipPhone`): name from `displayName` or `cn`,
+/// department from `department` or `ou`, location from `l` and tags from `businessCategory`.
+#[cfg(not(target_arch = "wasm32"))]
+fn parse_ldif(text: &str) -> Vec<Contact> {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+
+    // Lines starting with a space continue the previous one
+    let text = text.replace("\r\n", "\n").replace("\n ", "");
+
+    text.split("\n\n").filter_map(|record| {
+        let attrs = record.lines()
+            .filter(|x| !x.starts_with('#'))
+            .filter_map(|x| match x.split_once("::") {
+                Some((key, value)) => Some((key, STANDARD.decode(value.trim()).ok().and_then(|x| String::from_utf8(x).ok())?)),
+                None => x.split_once(':').map(|(key, value)| (key, value.trim().to_owned()))
+            })
+            .collect::<Vec<_>>();
+        let get = |names: &[&str]| names.iter().find_map(|name| attrs.iter().find(|(key, _)| key.eq_ignore_ascii_case(name))).map(|(_, value)| value.clone());
+
+        Some(Contact {
+            name: get(&["telephoneNumber", "ipPhone"])?,
+            display_name: get(&["displayName", "cn"]),
+            department: get(&["department", "ou"]),
+            location: get(&["l"]),
+            tags: attrs.iter().filter(|(key, _)| key.eq_ignore_ascii_case("businessCategory")).map(|(_, value)| value.clone()).collect(),
+            ..Default::default()
+        })
+    }).collect()
+}
+
+/// Takes the display name of `EndpointDetail` (`Callerid: "Maria" <200>`) for the entries the file didn't name.
+pub fn treat_directory(val: &str, directory: &Directory) {
+    let [b'E', b'v', b'e', b'n', b't', b':', b' ', b'E', b'n', b'd', b'p', b'o', b'i', b'n', b't', b'D', b'e', b't', b'a', b'i', b'l', b'\r', ..] = val.as_bytes() else { return };
+    let v = get_map(val);
+    let (Some(exten), Some(callerid)) = (v.get("ObjectName"), v.get("Callerid")) else { return };
+    let name = callerid.split('<').next().unwrap_or_default().trim().trim_matches('"').trim();
+
+    if name.is_empty() {
+        return
+    }
+
+    let mut directory = directory.lock().unwrap();
+    let entry = directory.entry(exten.to_string()).or_insert_with(|| Contact::from_name(*exten));
+
+    if entry.display_name.is_none() {
+        entry.display_name = Some(name.to_owned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_fields_can_be_quoted() {
+        assert_eq!(split_csv(r#"2001,"Souza, Maria","Sales ""East""",,vip;night"#), ["2001", "Souza, Maria", "Sales \"East\"", "", "vip;night"]);
+        assert_eq!(split_csv(""), [""]);
+    }
+
+    #[test]
+    fn csv_columns_come_from_the_header() {
+        let entries = parse_csv("Name,Exten,Tags,Location\r\n\"Souza, Maria\",2001, vip ; night ,2nd floor\r\n\r\nNo extension,,,\r\nJoão,2002,,\r\n").unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!((&entries[0].name[..], entries[0].display_name.as_deref()), ("2001", Some("Souza, Maria")));
+        assert_eq!((&entries[0].tags[..], entries[0].location.as_deref(), entries[0].department.as_deref()), (&["vip".to_owned(), "night".to_owned()][..], Some("2nd floor"), None));
+        assert_eq!((&entries[1].name[..], entries[1].display_name.as_deref(), entries[1].location.as_deref()), ("2002", Some("João"), None));
+        assert!(entries[1].tags.is_empty());
+
+        assert!(parse_csv("name,department\nMaria,Sales\n").is_err());
+    }
+
+    #[test]
+    fn ldif_folds_lines_and_decodes_base64() {
+        let entries = parse_ldif(concat!(
+            "# exported\n",
+            "dn: cn=Maria Souza,ou=Sales,dc=example,dc=com\n",
+            "cn: Maria Souza\n",
+            "displayName: Maria Souza - Sales Manager for the Northeast Re\n",
+            " gion\n",
+            "ou: Sales\n",
+            "telephoneNumber: 2001\n",
+            "businessCategory: vip\n",
+            "businessCategory: night\n",
+            "\n",
+            "dn: cn=Jose Souza,dc=example,dc=com\n",
+            "cn:: Sm9zw6kgU291emE=\n",
+            "ipPhone: 2002\n",
+            "l: Recife\n",
+            "\n",
+            "dn: cn=printer,dc=example,dc=com\n",
+            "cn: printer\n"
+        ));
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].display_name.as_deref(), Some("Maria Souza - Sales Manager for the Northeast Region"));
+        assert_eq!((&entries[0].name[..], entries[0].department.as_deref()), ("2001", Some("Sales")));
+        assert_eq!(entries[0].tags, ["vip", "night"]);
+        assert_eq!((&entries[1].name[..], entries[1].display_name.as_deref(), entries[1].location.as_deref()), ("2002", Some("José Souza"), Some("Recife")));
+    }
+
+    #[test]
+    fn endpoint_names_fill_what_the_file_left_out() {
+        let directory = Directory::default();
+
+        merge_directory(&directory, vec![Contact { display_name: Some("Maria".to_owned()), ..Contact::from_name("2001") }]);
+        treat_directory("Event: EndpointDetail\r\nObjectName: 2001\r\nCallerid: \"Maria Souza\" <2001>\r\n", &directory);
+        treat_directory("Event: EndpointDetail\r\nObjectName: 2002\r\nCallerid: \"João\" <2002>\r\n", &directory);
+        treat_directory("Event: EndpointDetail\r\nObjectName: 2003\r\nCallerid: <2003>\r\n", &directory);
+        treat_directory("Event: EndpointDetailComplete\r\nObjectName: 2004\r\nCallerid: \"Ana\" <2004>\r\n", &directory);
+
+        let directory = directory.lock().unwrap();
+        assert_eq!(directory["2001"].display_name.as_deref(), Some("Maria"));
+        assert_eq!(directory["2002"].display_name.as_deref(), Some("João"));
+        assert_eq!(directory.len(), 2);
+    }
+}
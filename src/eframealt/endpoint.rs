@@ -0,0 +1,170 @@
+use crate::*;
+use std::thread;
+use eframe::egui::{
+    self,
+    Color32,
+    TextEdit
+};
+
+type Pending = Arc<Mutex<Option<std::result::Result<EndpointDetail, String>>>>;
+
+/// Detail window of the extension whose tile was clicked.
+pub struct EndpointPanel {
+    pub exten: String,
+    detail: Pending,
+    /// Targets being typed for CF, CFB and CFU
+    forward: [String; 3],
+    /// Last DND or forwarding change that failed
+    error: Arc<Mutex<Option<String>>>
+}
+
+impl EndpointPanel {
+    /// Asks for the detail in the background, the window shows it once it arrives.
+    pub fn open(monitor: &Arc<Monitor>, exten: String) -> Self {
+        let panel = Self { exten, detail: Default::default(), forward: Default::default(), error: Default::default() };
+        panel.refresh(monitor);
+
+        panel
+    }
+
+    fn refresh(&self, monitor: &Arc<Monitor>) {
+        let (monitor, exten, detail) = (Arc::clone(monitor), self.exten.clone(), Arc::clone(&self.detail));
+        *detail.lock().unwrap() = None;
+
+        thread::spawn(move || {
+            let result = monitor.endpoint_detail(&exten).map_err(|e| e.to_string());
+            *detail.lock().unwrap() = Some(result);
+        });
+    }
+
+    /// `operator` allows changing DND and forwarding.
+    pub fn show(&mut self, ctx: &egui::Context, open: &mut bool, monitor: &Arc<Monitor>, operator: bool) {
+        egui::Window::new(format!("Endpoint {}", self.exten)).open(open).default_width(480.).show(ctx, |ui| {
+            if ui.button("Refresh").clicked() {
+                self.refresh(monitor);
+            }
+
+            let current = monitor.features.lock().unwrap().get(&self.exten).cloned().unwrap_or_default();
+            let mut change = None;
+
+            egui::Grid::new("endpoint features").striped(true).show(ui, |ui| {
+                let mut dnd = current.dnd;
+
+                ui.strong(Feature::Dnd.to_string());
+                if ui.add_enabled(operator, egui::Checkbox::new(&mut dnd, "")).changed() {
+                    change = Some((Feature::Dnd, dnd.then(|| "YES".to_owned())));
+                }
+                ui.end_row();
+
+                for (feature, target) in [Feature::Cf, Feature::Cfb, Feature::Cfu].into_iter().zip(&mut self.forward) {
+                    ui.strong(feature.to_string());
+                    ui.label(current.get(feature).unwrap_or("-"));
+
+                    if operator {
+                        ui.add(TextEdit::singleline(target).hint_text("Target").desired_width(80.));
+
+                        if ui.button("Set").clicked() && !target.trim().is_empty() {
+                            change = Some((feature, Some(target.trim().to_owned())));
+                        }
+                        if ui.add_enabled(current.get(feature).is_some(), egui::Button::new("Clear")).clicked() {
+                            change = Some((feature, None));
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+
+            if let Some((feature, val)) = change {
+                let (monitor, exten, error) = (Arc::clone(monitor), self.exten.clone(), Arc::clone(&self.error));
+                *error.lock().unwrap() = None;
+
+                thread::spawn(move || {
+                    if let Err(e) = monitor.set_feature(&exten, feature, val.as_deref()) {
+                        *error.lock().unwrap() = Some(format!("{feature}: {e}"));
+                    }
+                });
+            }
+
+            if let Some(e) = &*self.error.lock().unwrap() {
+                ui.colored_label(Color32::LIGHT_RED, e);
+            }
+            ui.separator();
+
+            let detail = self.detail.lock().unwrap();
+            let detail = match &*detail {
+                None => {
+                    ui.spinner();
+                    return
+                },
+                Some(Err(e)) => {
+                    ui.colored_label(Color32::LIGHT_RED, e);
+                    return
+                },
+                Some(Ok(detail)) => detail
+            };
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                egui::Grid::new("endpoint").num_columns(2).striped(true).show(ui, |ui| {
+                    let nat = &detail.nat;
+
+                    for (key, value) in [
+                        ("Caller ID", &detail.callerid),
+                        ("Context", &detail.context),
+                        ("Device state", &detail.device_state),
+                        ("Codecs", &detail.codecs.join(", ")),
+                        ("Transport", &detail.transport),
+                        ("RTP symmetric", &nat.rtp_symmetric),
+                        ("Force rport", &nat.force_rport),
+                        ("Rewrite contact", &nat.rewrite_contact),
+                        ("Direct media", &nat.direct_media),
+                        ("ICE", &nat.ice_support),
+                        ("Media address", &nat.media_address)
+                    ] {
+                        ui.strong(key);
+                        ui.label(value);
+                        ui.end_row();
+                    }
+                });
+
+                for transport in &detail.transports {
+                    ui.label(format!(
+                        "{} {} {} (external {} / {}, local net {})",
+                        transport.name, transport.protocol, transport.bind, transport.external_signaling_address, transport.external_media_address, transport.local_net
+                    ));
+                }
+
+                ui.separator();
+                ui.strong("Contacts");
+                egui::Grid::new("endpoint contacts").striped(true).show(ui, |ui| {
+                    for header in ["URI", "Status", "RTT", "User agent", "Via"] {
+                        ui.strong(header);
+                    }
+                    ui.end_row();
+
+                    for contact in &detail.contacts {
+                        let rtt = contact.rtt_usec.parse::<f64>().map_or_else(|_| "-".to_owned(), |x| format!("{:.1} ms", x / 1000.));
+
+                        ui.label(&contact.uri);
+                        ui.label(&contact.status);
+                        ui.label(rtt);
+                        ui.label(&contact.user_agent);
+                        ui.label(&contact.via);
+                        ui.end_row();
+                    }
+                });
+
+                ui.separator();
+                ui.strong("Active channels");
+                egui::Grid::new("endpoint channels").striped(true).show(ui, |ui| {
+                    for channel in monitor.channels.lock().unwrap().values().filter(|x| x.endpoint() == self.exten) {
+                        ui.label(&channel.channel);
+                        ui.label(&channel.state);
+                        ui.label(format!("{} → {}", channel.caller, channel.connected));
+                        ui.label(format!("{}s", unix_now().saturating_sub(channel.created)));
+                        ui.end_row();
+                    }
+                });
+            });
+        });
+    }
+}
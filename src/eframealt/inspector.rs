@@ -0,0 +1,113 @@
+use crate::*;
+use eframe::egui::{
+    self,
+    Color32,
+    RichText,
+    TextEdit
+};
+
+/// State of the AMI inspector window.
+#[derive(Default)]
+pub struct Inspector {
+    /// Event names to show, comma separated; empty shows everything
+    filter: String,
+    paused: bool,
+    /// Frames frozen while paused
+    held: Vec<RawFrame>,
+    action: String,
+    /// `Key: Value` per line
+    headers: String,
+    sent: Option<std::result::Result<u64, String>>
+}
+
+impl Inspector {
+    pub fn show(&mut self, ctx: &egui::Context, open: &mut bool, monitor: &Monitor) {
+        egui::Window::new("AMI").open(open).default_size([640., 560.]).show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.add(TextEdit::singleline(&mut self.filter).hint_text("ExtensionStatus, Hangup, ..."));
+
+                if ui.checkbox(&mut self.paused, "Pause").changed() && self.paused {
+                    self.held = monitor.frames.lock().unwrap().iter().cloned().collect();
+                }
+                if ui.button("Clear").clicked() {
+                    monitor.frames.lock().unwrap().clear();
+                    self.held.clear();
+                }
+            });
+            ui.separator();
+
+            let filter = self.filter.split(',').map(|x| x.trim().to_lowercase()).filter(|x| !x.is_empty()).collect::<Vec<_>>();
+            let wanted = |frame: &&RawFrame| filter.is_empty() || filter.iter().any(|x| frame.name().to_lowercase().contains(x));
+
+            egui::ScrollArea::vertical()
+                .id_source("frames")
+                .max_height((ui.available_height() - 200.).max(100.))
+                .auto_shrink([false; 2])
+                .stick_to_bottom(!self.paused)
+                .show(ui, |ui| {
+                    if self.paused {
+                        for frame in self.held.iter().filter(wanted) {
+                            frame_ui(ui, frame);
+                        }
+                    } else {
+                        for frame in monitor.frames.lock().unwrap().iter().filter(wanted) {
+                            frame_ui(ui, frame);
+                        }
+                    }
+                });
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.label("Action");
+                ui.add(TextEdit::singleline(&mut self.action).hint_text("PJSIPShowEndpoint"));
+
+                if ui.button("Send").clicked() && !self.action.trim().is_empty() {
+                    let headers = self.headers.lines()
+                        .filter_map(|x| x.split_once(':'))
+                        .map(|(k, v)| (k.trim(), v.trim()))
+                        .collect::<Vec<_>>();
+
+                    self.sent = Some(monitor.action(self.action.trim(), &headers).map_err(|e| e.to_string()));
+                }
+            });
+            ui.add(TextEdit::multiline(&mut self.headers).hint_text("Endpoint: 200").desired_rows(2).code_editor());
+
+            match &self.sent {
+                Some(Ok(id)) => {
+                    let frames = monitor.frames.lock().unwrap();
+                    let replies = frames.iter().filter(|x| x.dir == Direction::In && x.action_id() == Some(*id)).collect::<Vec<_>>();
+
+                    ui.label(format!("ActionID {id}: {} frames", replies.len()));
+                    egui::ScrollArea::vertical().id_source("reply").auto_shrink([false; 2]).show(ui, |ui| {
+                        for frame in replies {
+                            frame_ui(ui, frame);
+                        }
+                    });
+                },
+                Some(Err(e)) => {
+                    ui.colored_label(Color32::LIGHT_RED, e);
+                },
+                None => ()
+            }
+        });
+    }
+}
+
+/// One line per frame, expanding to its headers.
+fn frame_ui(ui: &mut egui::Ui, frame: &RawFrame) {
+    let (arrow, color) = match frame.dir {
+        Direction::Out => ("→", Color32::LIGHT_BLUE),
+        _ => ("←", ui.visuals().text_color())
+    };
+    let title = RichText::new(format!("{} {arrow} {}", &rfc3339(frame.at)[11..19], frame.name())).color(color).monospace();
+
+    egui::CollapsingHeader::new(title).id_source(frame.seq).show(ui, |ui| {
+        egui::Grid::new(("headers", frame.seq)).num_columns(2).striped(true).show(ui, |ui| {
+            for (key, value) in frame.headers() {
+                ui.strong(key);
+                ui.monospace(value);
+                ui.end_row();
+            }
+        });
+    });
+}
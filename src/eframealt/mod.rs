@@ -1,17 +1,32 @@
+#[cfg(windows)]
+mod endpoint;
+#[cfg(windows)]
+mod inspector;
+#[cfg(windows)]
+mod wallboard;
+
+#[cfg(windows)]
+pub use self::endpoint::*;
+#[cfg(windows)]
+pub use self::inspector::*;
+
 use crate::*;
 use std::{
+    time::Duration,
     sync::{
-        mpsc,
         Arc,
         Mutex
     },
     collections::{
-        BTreeMap
+        BTreeMap,
+        HashMap,
+        HashSet
     },
     ops::{
         Deref,
         DerefMut
     },
+    fmt,
 };
 use serde::*;
 use eframe::{
@@ -25,6 +40,11 @@ use eframe::{
     },
 };
 #[cfg(target_arch = "wasm32")]
+use std::{
+    cell::RefCell,
+    rc::Rc
+};
+#[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 #[cfg(target_arch = "wasm32")]
 use web_sys::{MessageEvent, WebSocket, BinaryType};
@@ -47,96 +67,431 @@ pub struct SipMonitor {
     cred: Cred,
     conf: Config,
     state: StateScreen,
+    settings: Settings,
+    regs: Registrations,
+    directory: Directory,
+    alerts: Alerts,
+    trunks: Trunks,
+    channels: Channels,
+    features: Features,
+    parking: Parking,
+    conferences: Conferences,
+    /// Themes of the settings, then the built in ones they don't replace
+    themes: Vec<Theme>,
+    /// Extension whose call is being dragged onto another tile
+    dragging: Option<String>,
+    /// Last operator action that failed
+    op_error: Arc<Mutex<Option<String>>>,
+    #[cfg(windows)]
+    conn: Option<Arc<Monitor>>,
+    /// Login running in the background
+    #[cfg(windows)]
+    login: Option<Receiver<Result<Arc<Monitor>>>>,
+    /// Why the wallboard couldn't log in and when it tried
     #[cfg(windows)]
-    conn: Option<(Ami, Data)>,
+    login_error: Option<(String, std::time::Instant)>,
+    #[cfg(windows)]
+    inspector: Inspector,
+    #[cfg(windows)]
+    endpoint: Option<EndpointPanel>,
+    #[cfg(target_arch = "wasm32")]
+    data: Option<Data>,
     #[cfg(target_arch = "wasm32")]
-    data: Option<Data>
+    ws_url: String
 }
 
 impl SipMonitor {
-    pub fn new(_: &eframe::CreationContext<'_>) -> Self {
-        Self::default()
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        Self::with_settings(cc, Settings::load())
+    }
+
+    pub fn with_settings(_: &eframe::CreationContext<'_>, settings: Settings) -> Self {
+        let conf = Config { theme: settings.theme.clone(), dark: settings.dark, ..Default::default() };
+        let themes = Theme::with_builtin(&settings.themes);
+
+        Self {
+            conf,
+            themes,
+            settings,
+            ..Default::default()
+        }
+    }
+
+    /// Browser client fed by the bridge websocket at `ws_url`.
+    #[cfg(target_arch = "wasm32")]
+    pub fn web(cc: &eframe::CreationContext<'_>, ws_url: String) -> Self {
+        Self {
+            ws_url,
+            ..Self::with_settings(cc, Settings::default())
+        }
     }
 }
 
 
 impl eframe::App for SipMonitor {
    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        if ctx.style().visuals.dark_mode != self.conf.dark {
+            ctx.set_visuals(if self.conf.dark { egui::Visuals::dark() } else { egui::Visuals::light() });
+        }
+
+        #[cfg(windows)]
+        if self.settings.wallboard.is_some() {
+            return self.wallboard(ctx)
+        }
+
+        let authorized = self.settings.operators.contains(&self.cred.user);
         let Cred { user, pass, addr } = &mut self.cred;
-        let Config { color, size } = &mut self.conf;
+        let themes = &self.themes;
+        let Config { theme, dark, size, registrations, ami, operator, filter, group } = &mut self.conf;
         let pass2 = TextEdit::singleline(pass).password(true);
 
-        egui::CentralPanel::default().show(ctx, |ui| {
+        let alerts = self.alerts.lock().unwrap().clone();
+
+        if !alerts.is_empty() {
+            egui::TopBottomPanel::top("alerts").frame(Frame::default().fill(Color32::DARK_RED).inner_margin(4.)).show(ctx, |ui| {
+                for alert in alerts {
+                    ui.colored_label(Color32::WHITE, format!("⚠ {:?} - {}: {}", alert.severity, alert.rule, alert.message));
+                }
+            });
+        }
+
+        let mut link: Option<Link> = None;
+
+        #[cfg(windows)]
+        {
+            link = self.conn.as_ref().map(|x| if x.stats.connected.load(std::sync::atomic::Ordering::Relaxed) { Link::Connected } else { Link::Connecting })
+                .or(self.login.as_ref().map(|_| Link::Connecting));
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            link = self.data.as_ref().map(|x| *x.link.lock().unwrap());
+        }
+
+        let trunks = self.trunks.lock().unwrap().values().cloned().collect::<Vec<_>>();
+
+        if !trunks.is_empty() {
+            let channels = self.channels.lock().unwrap();
+
+            egui::TopBottomPanel::top("trunks").show(ctx, |ui| {
+                ui.horizontal_wrapped(|ui| {
+                    for trunk in &trunks {
+                        trunk_ui(ui, trunk, trunk.usage(&channels));
+                    }
+                });
+            });
+        }
+
+        let parking = self.parking.lock().unwrap().clone();
+        let conferences = self.conferences.lock().unwrap().clone();
+
+        // Before the central panel, so the grid fits beside it
+        if !parking.is_empty() || !conferences.is_empty() {
+            egui::SidePanel::right("parking and conferences").show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    parking_ui(ui, &parking);
+                    ui.separator();
+                    conferences_ui(ui, &conferences);
+                });
+            });
+        }
+
+        // Tiles keep the last known state, greyed out, until the link is back
+        let stale = link.map_or(false, |x| x != Link::Connected);
+
+        let header = egui::CentralPanel::default().show(ctx, |ui| {
             ui.horizontal(|ui| {
                 ui.label("SipMonitor");
-                ui.checkbox(color, "Color");
+                egui::ComboBox::from_label("Theme").selected_text(theme.as_str()).show_ui(ui, |ui| {
+                    for name in themes.iter().map(|x| &x.name) {
+                        ui.selectable_value(theme, name.clone(), name);
+                    }
+                });
+                ui.checkbox(dark, "Dark");
+                ui.checkbox(registrations, "Registrations");
+                #[cfg(windows)]
+                ui.checkbox(ami, "AMI");
+                #[cfg(windows)]
+                if authorized {
+                    ui.checkbox(operator, "Operator");
+                }
+                ui.add(TextEdit::singleline(filter).hint_text("Filter").desired_width(120.));
+                egui::ComboBox::from_label("Group").selected_text(group.to_string()).show_ui(ui, |ui| {
+                    for by in GroupBy::ALL {
+                        ui.selectable_value(group, by, by.to_string());
+                    }
+                });
+
+                if let Some(link) = link {
+                    ui.colored_label(link.color(), format!("● {link}"));
+                }
+                if let Some(e) = &*self.op_error.lock().unwrap() {
+                    ui.colored_label(Color32::LIGHT_RED, format!("⚠ {e}"));
+                }
             });
             *size = ui.available_size();
-        });
+
+            ui.min_rect().bottom()
+        }).inner;
 
 
         if let StateScreen::Logged = self.state {
+            // State changes arrive from the AMI thread, not from input
+            ctx.request_repaint_after(Duration::from_secs(1));
+
             #[cfg(windows)]
             if let None = self.conn {
-                self.conn = Some(login2(&self.cred));
+                let login = self.login.get_or_insert_with(|| {
+                    *self.op_error.lock().unwrap() = None;
+                    spawn_login(login2(&self.cred, &self.settings))
+                });
+
+                match login.try_recv() {
+                    Ok(Ok(monitor)) => {
+                        self.regs = Arc::clone(&monitor.regs);
+                        self.directory = Arc::clone(&monitor.directory);
+                        self.alerts = Arc::clone(&monitor.alerts);
+                        self.trunks = Arc::clone(&monitor.trunks);
+                        self.channels = Arc::clone(&monitor.channels);
+                        self.features = Arc::clone(&monitor.features);
+                        self.parking = Arc::clone(&monitor.parking);
+                        self.conferences = Arc::clone(&monitor.conferences);
+                        self.conn = Some(monitor);
+                        self.login = None;
+                    },
+                    Ok(Err(e)) => {
+                        // Back to the credentials, with the reason
+                        *self.op_error.lock().unwrap() = Some(format!("Login failed: {e}"));
+                        self.state = StateScreen::Login;
+                        self.login = None;
+                    },
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        *self.op_error.lock().unwrap() = Some("Login failed".to_owned());
+                        self.state = StateScreen::Login;
+                        self.login = None;
+                    },
+                    Err(mpsc::TryRecvError::Empty) => ()
+                }
             }
 
             #[cfg(target_arch = "wasm32")]
             if let None = self.data {
-                self.data = Some(login3(&self.cred));
+                self.data = Some(login3(&self.cred, &self.ws_url));
             }
 
-            let mut data: Option<&Data> = None;
+            let mut data: Option<&AllData> = None;
 
             #[cfg(windows)]
             {
-                let (_, d) = &self.conn.as_ref().unwrap();
-                data = Some(d);
+                data = self.conn.as_ref().map(|x| &x.data);
             }
 
             #[cfg(target_arch = "wasm32")]
             {
-                let d = &self.data.as_ref().unwrap();
-                data = Some(d);
+                data = self.data.as_ref().map(|x| &x.map);
             }
 
             let width = WITDH + 20.;
-            let len = (size.x / width).floor() * width;
-
-            for (idx, (contact, status)) in data.unwrap().lock().unwrap().iter().enumerate() {
-                let frame = Frame::window(&ctx.style()).fill({
-                    if !*color {
-                        match status.status {
-                            0 => Color32::DARK_GREEN,
-                            1 | 9 => Color32::DARK_BLUE,
-                            2 | 4 => Color32::DARK_RED,
-                            8 => Color32::YELLOW,
-                            16 | 17 => Color32::BROWN,
-                            _ => Color32::DARK_GRAY
-                        }
-                    } else {
-                        match status.status {
-                            0 => Color32::DARK_BLUE,
-                            1 | 9 => Color32::GOLD,
-                            2 | 4 => Color32::DARK_RED,
-                            8 => Color32::YELLOW,
-                            16 | 17 => Color32::KHAKI,
-                            _ => Color32::DARK_GRAY
-                        }
+            let per_row = (size.x / width).floor().max(1.) as usize;
+            // Copies, so the frame holds no lock while the AMI thread and the rules engine take theirs
+            let regs = self.regs.lock().unwrap().clone();
+            let mut by_aor = HashMap::<&str, Vec<&Registration>>::new();
+
+            for reg in regs.values() {
+                by_aor.entry(&reg.aor).or_default().push(reg);
+            }
+
+            let directory = self.directory.lock().unwrap().clone();
+            let features = self.features.lock().unwrap().clone();
+            let data = data.unwrap().lock().unwrap().clone();
+            let filter = filter.trim().to_lowercase();
+            let unknown = Contact::default();
+            let mut groups = BTreeMap::<&str, Vec<(&String, &SipStatus, Option<&Contact>)>>::new();
+
+            for (contact, status) in data.iter() {
+                let entry = directory.get(contact);
+
+                if !filter.is_empty() && !entry.map_or_else(|| contact.to_lowercase().contains(&filter), |x| x.matches(&filter)) {
+                    continue
+                }
+
+                for key in entry.unwrap_or(&unknown).groups(*group) {
+                    groups.entry(key).or_default().push((contact, status, entry));
+                }
+            }
+
+            // Below the header row and the panels above it
+            let mut top = header + 6.;
+            let palette = pick_theme(themes, theme);
+            let mut clicked = None;
+            // Drag and drop works on the tiles, only when there's someone to act as
+            let operating = *operator && authorized;
+            let own = self.settings.operator_exten.as_deref().filter(|_| operating);
+            let in_call = self.channels.lock().unwrap().values().filter(|x| x.state == "Up").map(|x| x.endpoint().to_owned()).collect::<HashSet<_>>();
+            let mut drag = None;
+            let mut tiles_at = Vec::new();
+
+            for (key, tiles) in &groups {
+                if *group != GroupBy::None {
+                    egui::Area::new(format!("group {key}")).fixed_pos(Pos2::new(20., top)).show(ctx, |ui| {
+                        ui.heading(*key);
+                    });
+                    top += 30.;
+                }
+
+                for (idx, (contact, status, entry)) in tiles.iter().enumerate() {
+                    let colors = palette.colors(status.state());
+                    let text = rgb(colors.text);
+                    let frame = Frame::window(&ctx.style()).fill(rgb(colors.fill));
+                    let frame = if stale { frame.fill(frame.fill.linear_multiply(0.35)) } else { frame };
+
+                    // The icon tells the state apart without relying on the fill
+                    let tile = egui::Window::new(RichText::new(format!("{} SIP: {}", status.state().icon(), contact)).color(text))
+                        // A tile shows up once per tag
+                        .id(egui::Id::new((key, contact)))
+                        .current_pos(Pos2::new((idx % per_row) as f32 * width + 20., (idx / per_row) as f32 * HEIGHT + top))
+                        .frame(frame)
+                        .movable(!operating)
+                        .show(ctx, |ui| {
+                            if let Some(name) = entry.and_then(|x| x.display_name.as_ref()) {
+                                ui.colored_label(text, RichText::new(name).strong());
+                            }
+                            if let Some(features) = features.get(&contact[..]).filter(|x| !x.is_empty()) {
+                                features_ui(ui, features);
+                            }
+                            for reg in by_aor.get(&contact[..]).into_iter().flatten() {
+                                let line = format!("{}{} {}", if reg.flagged() { "⚠ " } else { "" }, reg.status, reg.rtt_text());
+                                ui.colored_label(text, line);
+                            }
+                            // ui.label(format!("Status: {}", status.status));
+                            ui.colored_label(text, format!("StatusText: {}", status.status_text));
+                            if let Some(presence) = status.presence.as_ref().filter(|x| x.is_set()) {
+                                presence_ui(ui, presence);
+                            }
+                            if stale {
+                                ui.colored_label(text, "⚠ stale");
+                            }
+                            // ui.label(format!("x: {} - y: {}", size.x % (idx as f32 * (WITDH + 20.)) + 20., (size.y / (idx as f32 * (WITDH + 20.))).floor() * HEIGHT + 30.));
+                            ui.set_width(WITDH);
+
+                            let sense = if operating { egui::Sense::click_and_drag() } else { egui::Sense::click() };
+                            let hint = match status.state() {
+                                _ if !operating => "",
+                                _ if in_call.contains(&contact[..]) => "Drag onto another extension to transfer",
+                                ExtensionState::Idle if own.map_or(false, |x| x != &contact[..]) => "Click to call",
+                                ExtensionState::Ringing | ExtensionState::InUseRinging if own.is_some() => "Click to pick up",
+                                _ => ""
+                            };
+                            let res = ui.interact(ui.min_rect(), ui.id().with("detail"), sense)
+                                .on_hover_cursor(egui::CursorIcon::PointingHand);
+
+                            if hint.is_empty() { res } else { res.on_hover_text(hint) }
+                    });
+
+                    let Some(tile) = tile else { continue };
+                    tiles_at.push((contact.to_string(), tile.response.rect));
+                    let Some(res) = tile.inner else { continue };
+
+                    // In operator mode the detail is a right click away
+                    match status.state() {
+                        _ if res.secondary_clicked() => clicked = Some((contact.to_string(), TileAction::Detail)),
+                        _ if !res.clicked() => (),
+                        ExtensionState::Idle if operating && own.map_or(false, |x| x != &contact[..]) => clicked = Some((contact.to_string(), TileAction::Dial)),
+                        ExtensionState::Ringing | ExtensionState::InUseRinging if operating && own.is_some() => clicked = Some((contact.to_string(), TileAction::Pickup)),
+                        _ => clicked = Some((contact.to_string(), TileAction::Detail))
                     }
-                });
 
-                egui::Window::new(RichText::new(format!("SIP: {}", contact)).color(Color32::WHITE))
-                    .current_pos(Pos2::new(idx as f32 * width % len + 20., (idx as f32 * width / len).floor() * HEIGHT + 30.))
-                    .frame(frame)
-                    .show(ctx, |ui| {
-                        // ui.label(format!("Contact: {}", contact.contact));
-                        // ui.label(format!("Status: {}", status.status));
-                        ui.colored_label(Color32::WHITE, format!("StatusText: {}", status.status_text));
-                        // ui.label(format!("x: {} - y: {}", size.x % (idx as f32 * (WITDH + 20.)) + 20., (size.y / (idx as f32 * (WITDH + 20.))).floor() * HEIGHT + 30.));
-                        ui.set_width(WITDH);
+                    if res.drag_started() && in_call.contains(&contact[..]) {
+                        drag = Some(contact.to_string());
+                    }
+                }
+
+                top += ((tiles.len() + per_row - 1) / per_row) as f32 * HEIGHT;
+            }
+
+            let mut dropped = None;
+
+            if drag.is_some() {
+                self.dragging = drag;
+            }
+
+            if let Some(from) = self.dragging.clone() {
+                let pointer = ctx.input(|i| i.pointer.interact_pos());
+
+                if let Some(pos) = pointer {
+                    egui::Area::new("transfer").order(egui::Order::Tooltip).fixed_pos(pos + Vec2::new(12., 12.)).show(ctx, |ui| {
+                        ui.label(RichText::new(format!("↪ {from}")).strong());
+                    });
+                }
+
+                if ctx.input(|i| i.pointer.any_released()) {
+                    self.dragging = None;
+                    dropped = pointer
+                        .and_then(|pos| tiles_at.iter().find(|(_, rect)| rect.contains(pos)))
+                        .map(|(to, _)| (from.clone(), to.clone()))
+                        .filter(|(_, to)| *to != from);
+                }
+            }
+
+            if *registrations {
+                egui::Window::new("Registrations").show(ctx, |ui| {
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        egui::Grid::new("registrations").striped(true).show(ui, |ui| {
+                            for header in ["AOR", "URI", "User agent", "Status", "RTT", "Expires"] {
+                                ui.strong(header);
+                            }
+                            ui.end_row();
+
+                            for reg in regs.values() {
+                                let color = if reg.flagged() { Color32::LIGHT_RED } else { ui.visuals().text_color() };
+
+                                ui.colored_label(color, &reg.aor);
+                                ui.colored_label(color, &reg.uri);
+                                ui.colored_label(color, &reg.user_agent);
+                                ui.colored_label(color, reg.status.to_string());
+                                ui.colored_label(color, reg.rtt_text());
+                                ui.colored_label(color, format!("{}s", reg.expires_in()));
+                                ui.end_row();
+                            }
+                        });
+                    });
                 });
             }
+
+            #[cfg(windows)]
+            if let Some(monitor) = &self.conn {
+                if *ami {
+                    self.inspector.show(ctx, ami, monitor);
+                }
+
+                let context = self.settings.dial_context.clone();
+
+                match (clicked, own) {
+                    (Some((exten, TileAction::Dial)), Some(own)) => {
+                        let own = own.to_owned();
+                        operate(monitor, &self.op_error, move |x| x.dial(&own, &exten, &context));
+                    },
+                    (Some((exten, TileAction::Pickup)), Some(own)) => {
+                        let own = own.to_owned();
+                        operate(monitor, &self.op_error, move |x| x.pickup(&own, &exten));
+                    },
+                    (Some((exten, _)), _) => self.endpoint = Some(EndpointPanel::open(monitor, exten)),
+                    (None, _) => ()
+                }
+
+                if let Some((from, to)) = dropped {
+                    let context = self.settings.dial_context.clone();
+                    operate(monitor, &self.op_error, move |x| x.transfer(&from, &to, &context));
+                }
+
+                let mut open = true;
+                if let Some(panel) = &mut self.endpoint {
+                    panel.show(ctx, &mut open, monitor, authorized);
+                }
+                if !open {
+                    self.endpoint = None;
+                }
+            }
         } else {
             egui::Window::new("Credentials").show(ctx, |ui| {
                 ui.label("Address");
@@ -145,6 +500,9 @@ impl eframe::App for SipMonitor {
                 ui.text_edit_singleline(user);
                 ui.label("Pass");
                 pass2.show(ui);
+                if let Some(e) = &*self.op_error.lock().unwrap() {
+                    ui.colored_label(Color32::LIGHT_RED, format!("⚠ {e}"));
+                }
                 if ui.button("Login").clicked() {
                     self.state = StateScreen::Logged;
                 }
@@ -153,19 +511,206 @@ impl eframe::App for SipMonitor {
    }
 }
 
-pub struct Data(pub AllData);
+/// Theme called `name`, or the default one.
+fn pick_theme<'a>(themes: &'a [Theme], name: &str) -> &'a Theme {
+    themes.iter()
+        .find(|x| x.name == name)
+        .or_else(|| themes.iter().find(|x| x.name == DEFAULT_THEME))
+        .unwrap()
+}
+
+fn rgb(val: Rgb) -> Color32 {
+    Color32::from_rgb(val.0, val.1, val.2)
+}
+
+/// What a click on a tile does.
+enum TileAction {
+    /// Opens the endpoint panel
+    Detail,
+    /// Calls the extension from the operator's
+    Dial,
+    /// Takes the call ringing at the extension
+    Pickup
+}
+
+/// Runs an operator action in the background, its error shows up in the header.
+#[cfg(windows)]
+fn operate<F>(monitor: &Arc<Monitor>, error: &Arc<Mutex<Option<String>>>, op: F)
+    where F: FnOnce(&Monitor) -> Result<()> + Send + 'static
+{
+    let (monitor, error) = (Arc::clone(monitor), Arc::clone(error));
+    *error.lock().unwrap() = None;
+
+    std::thread::spawn(move || {
+        if let Err(e) = op(&monitor) {
+            *error.lock().unwrap() = Some(e.to_string());
+        }
+    });
+}
+
+/// Secondary indicator of the tile: presence and its message, if any.
+fn presence_ui(ui: &mut egui::Ui, presence: &Presence) {
+    let color = match presence.state {
+        PresenceKind::Available => Color32::LIGHT_GREEN,
+        PresenceKind::Away | PresenceKind::Xa => Color32::GOLD,
+        PresenceKind::Dnd | PresenceKind::Unavailable => Color32::LIGHT_RED,
+        PresenceKind::Chat => Color32::LIGHT_BLUE,
+        _ => Color32::LIGHT_GRAY
+    };
+    let text = match &presence.message[..] {
+        "" => format!("◆ {}", presence.state),
+        message => format!("◆ {}: {message}", presence.state)
+    };
+
+    ui.colored_label(color, RichText::new(text).small());
+}
+
+/// Badges of the tile: DND and the forwarding targets.
+fn features_ui(ui: &mut egui::Ui, features: &CallFeatures) {
+    ui.horizontal_wrapped(|ui| {
+        if features.dnd {
+            ui.label(RichText::new("⛔ DND").color(Color32::WHITE).background_color(Color32::RED).small());
+        }
+
+        for (feature, label) in [(Feature::Cf, "↪"), (Feature::Cfb, "↪ busy"), (Feature::Cfu, "↪ no answer")] {
+            if let Some(target) = features.get(feature) {
+                ui.label(RichText::new(format!("{label} {target}")).color(Color32::BLACK).background_color(Color32::LIGHT_BLUE).small());
+            }
+        }
+    });
+}
+
+fn parking_ui(ui: &mut egui::Ui, parking: &BTreeMap<String, ParkingLot>) {
+    ui.heading("Parking");
+
+    for lot in parking.values() {
+        let free = lot.free().map_or_else(String::new, |x| format!(" - {x} free"));
+
+        ui.strong(format!("{}{free}", lot.name));
+        egui::Grid::new(("parking", &lot.name)).striped(true).show(ui, |ui| {
+            for call in lot.calls.values() {
+                let left = call.timeout.saturating_sub(unix_now());
+
+                ui.label(&call.space);
+                ui.label(format!("{} {}", call.caller, call.caller_name));
+                ui.label(format!("{}s", unix_now().saturating_sub(call.parked)));
+                ui.colored_label(if left < 10 { Color32::LIGHT_RED } else { ui.visuals().text_color() }, format!("↩ {left}s"))
+                    .on_hover_text(format!("Goes back to {}", call.parker));
+                ui.end_row();
+            }
+        });
+    }
+}
+
+fn conferences_ui(ui: &mut egui::Ui, conferences: &BTreeMap<String, Conference>) {
+    ui.heading("Conferences");
+
+    if conferences.is_empty() {
+        ui.weak("No active conferences");
+    }
+
+    for conference in conferences.values() {
+        let lock = if conference.locked { " 🔒" } else { "" };
+
+        egui::CollapsingHeader::new(format!("{} ({}){lock}", conference.name, conference.participants.len()))
+            .id_source(("conference", &conference.name))
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::Grid::new(("participants", &conference.name)).striped(true).show(ui, |ui| {
+                    for participant in conference.participants.values() {
+                        let (icon, color) = match (participant.muted, participant.talking) {
+                            (true, _) => ("🔇", Color32::GRAY),
+                            (false, true) => ("🔊", Color32::GREEN),
+                            (false, false) => ("🎤", ui.visuals().text_color())
+                        };
+                        let admin = if participant.admin { " ★" } else { "" };
+
+                        ui.colored_label(color, icon);
+                        ui.label(format!("{} {}{admin}", participant.caller, participant.caller_name));
+                        ui.label(format!("{}s", unix_now().saturating_sub(participant.joined)));
+                        ui.end_row();
+                    }
+                });
+            });
+    }
+}
+
+/// Box of the trunks strip: state, time since it changed and channels in use.
+fn trunk_ui(ui: &mut egui::Ui, trunk: &Trunk, usage: usize) {
+    let color = match trunk.state {
+        TrunkState::Registered | TrunkState::Reachable => Color32::DARK_GREEN,
+        TrunkState::Rejected | TrunkState::Unreachable => Color32::DARK_RED,
+        TrunkState::Unregistered => Color32::BROWN,
+        TrunkState::Unknown => Color32::DARK_GRAY
+    };
+    let since = match unix_now().saturating_sub(trunk.changed) {
+        _ if trunk.changed == 0 => "-".to_owned(),
+        x if x < 60 => format!("{x}s"),
+        x if x < 3600 => format!("{}m", x / 60),
+        x if x < 86400 => format!("{}h{:02}m", x / 3600, x % 3600 / 60),
+        x => format!("{}d", x / 86400)
+    };
+    let limit = trunk.limit.map_or_else(|| "-".to_owned(), |x| x.to_string());
+    // Full when the provider would refuse the next call
+    let full = trunk.limit.map_or(false, |x| usage >= x);
+
+    Frame::group(ui.style()).fill(color).show(ui, |ui| {
+        ui.colored_label(Color32::WHITE, RichText::new(&trunk.endpoint).strong());
+        ui.colored_label(Color32::WHITE, format!("{} · {since}", trunk.state));
+        ui.colored_label(if full { Color32::YELLOW } else { Color32::WHITE }, format!("{usage}/{limit} ch"));
+    }).response.on_hover_text(format!("{}\n{}", trunk.server_uri, trunk.cause).trim());
+}
+
+#[derive(Clone)]
+pub struct Data {
+    pub map: AllData,
+    pub link: Arc<Mutex<Link>>,
+    /// Handler of the current socket, dropped when the next one replaces it
+    #[cfg(target_arch = "wasm32")]
+    onmessage: Rc<RefCell<Option<Closure<dyn FnMut(MessageEvent)>>>>
+}
 
 impl Deref for Data {
     type Target = AllData;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.map
     }
 }
 
 impl DerefMut for Data {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.map
+    }
+}
+
+/// State of the connection feeding the tiles.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Link {
+    #[default]
+    Connecting,
+    Connected,
+    /// Waiting `delay` seconds before attempt number `attempt`
+    Reconnecting { attempt: u32, delay: u32 }
+}
+
+impl Link {
+    pub fn color(&self) -> Color32 {
+        match self {
+            Self::Connecting => Color32::YELLOW,
+            Self::Connected => Color32::GREEN,
+            Self::Reconnecting { .. } => Color32::LIGHT_RED
+        }
+    }
+}
+
+impl fmt::Display for Link {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Connecting => write!(f, "Connecting..."),
+            Self::Connected => write!(f, "Connected"),
+            Self::Reconnecting { attempt, delay } => write!(f, "Disconnected, retry #{attempt} in {delay}s")
+        }
     }
 }
 
@@ -178,7 +723,17 @@ pub struct Cred {
 
 #[derive(Default, Clone)]
 pub struct Config {
-    pub color: bool,
+    /// Name of the tile theme
+    pub theme: String,
+    pub dark: bool,
+    pub registrations: bool,
+    /// Raw AMI inspector window
+    pub ami: bool,
+    /// Click-to-dial, pickup and drag-to-transfer on the tiles
+    pub operator: bool,
+    /// Matched against the extension and its directory entry
+    pub filter: String,
+    pub group: GroupBy,
     pub size: Vec2
 }
 
@@ -189,93 +744,126 @@ pub enum StateScreen {
     Logged
 }
 
-fn login2(cred: &Cred) -> (Ami, Data) {
-    let ami = AmiConnect::new(cred.user.clone(), cred.pass.clone(), cred.addr.parse().unwrap(), 5038);
-    let mut ami = Ami::new(ami).unwrap();
+/// `settings` logging in with `cred` instead of their own `ami`; a replay in them still wins.
+#[cfg(windows)]
+fn login2(cred: &Cred, settings: &Settings) -> Settings {
+    let conf = AmiSettings {
+        addr: cred.addr.clone(),
+        user: cred.user.clone(),
+        pass: cred.pass.clone(),
+        port: settings.ami.as_ref().map_or(5038, |x| x.port),
+        record: settings.ami.as_ref().and_then(|x| x.record.clone())
+    };
+
+    Settings { ami: Some(conf), ..settings.clone() }
+}
+
+/// Logs in and bootstraps on another thread, so the window keeps drawing meanwhile.
+#[cfg(windows)]
+fn spawn_login(settings: Settings) -> Receiver<Result<Arc<Monitor>>> {
     let (send, recv) = mpsc::channel();
-    let map = Data(Arc::new(Mutex::new(BTreeMap::new())));
-    let sync_map = Arc::clone(&map);
-
-    ami.init_treat(move |val| {
-        // println!("{val}\n");
-        match val.as_bytes() {
-            [b'R', b'e', b's', b'p', b'o', b'n', b's', b'e', b':', b' ', b'S', b'u', b'c', b'c', b'e', b's', b's', ..] => {
-                let msg = process(&val);
-
-                send.send(msg).unwrap()
-            },
-            [b'R', b'e', b's', b'p', b'o', b'n', b's', b'e', b':', b' ', ..] => {
-                println!("{val}");
-                send.send(Message::Unknown).unwrap()
-            },
-            [b'E', b'v', b'e', b'n', b't', b':', b' ', b'A', b'o', b'r', b'L', b'i', b's', b't', b'C', b'o', b'm', b'p', b'l', b'e', b't', b'e', ..] => send.send(Message::Complete).unwrap(),
-            [b'E', b'v', b'e', b'n', b't', b':', b' ', b'A', b'o', b'r', b'L', b'i', b's', b't', ..] => {
-                let msg = contact(&val);
-
-                send.send(msg).unwrap()
-            },
-            [b'E', b'v', b'e', b'n', b't', b':', b' ', b'E', b'x', b't', b'e', b'n', b's', b'i', b'o', b'n', b'S', b't', b'a', b't', b'u', b's', ..] => {
-                let Message::Sip(sip, status) = process(&val) else { return };
-                sync_map.lock().unwrap()
-                    .entry(sip)
-                    .and_modify(|x| *x = status);
-
-                // send.send(Message::Updated).unwrap()
-            },
-            _ => ()
-        }
-    }).unwrap();
 
-    ami.pjsip_show_aors().unwrap();
-    let mut contacts = Vec::new();
+    std::thread::spawn(move || {
+        let _ = send.send(start_monitor(&settings));
+    });
 
-    if let Message::Start = recv.recv().unwrap() {
-        loop {
-            let msg = recv.recv().unwrap();
+    recv
+}
 
-            match msg {
-                Message::Complete => break,
-                Message::Contact { contact, name } => {
-                    contacts.push(Contact { contact, name });
-                }
-                _ => ()
-            }
-        }
-    }
+/// Longest wait between reconnection attempts of the browser client, in seconds.
+#[cfg(target_arch = "wasm32")]
+const MAX_RETRY: u32 = 30;
 
-    for contact in contacts {
-        ami.extension_state(&contact.name, "ext-local").unwrap();
+/// `ws://` or `wss://`, following the page, at `ws` next to it, e.g. `wss://host/sip/ws` for `https://host/sip/`.
+#[cfg(target_arch = "wasm32")]
+pub fn default_ws_url() -> String {
+    let Some(location) = web_sys::window().map(|x| x.location()) else { return "ws://127.0.0.1:61339/ws".to_owned() };
+    let host = location.host().unwrap_or_default();
+    let path = location.pathname().unwrap_or_default();
+    let dir = &path[..path.rfind('/').map_or(0, |x| x + 1)];
+    let scheme = match location.protocol().as_deref() {
+        Ok("https:") => "wss",
+        _ => "ws"
+    };
+
+    format!("{scheme}://{host}{}ws", if dir.is_empty() { "/" } else { dir })
+}
 
-        let Message::Sip(_, status) = recv.recv().unwrap() else { panic!() };
-        map.lock().unwrap().insert(contact.name, status);
-    }
+#[cfg(target_arch = "wasm32")]
+fn login3(cred: &Cred, url: &str) -> Data {
+    let data = Data {
+        map: Arc::new(Mutex::new(BTreeMap::new())),
+        link: Default::default(),
+        onmessage: Default::default()
+    };
 
-    (ami, map)
+    connect(Arc::from(url), cred.clone(), data.clone(), 0);
+
+    data
 }
 
+/// Opens the websocket and subscribes with `cred`. When it closes, tries again with backoff,
+/// keeping the last map so the tiles still show something.
 #[cfg(target_arch = "wasm32")]
-fn login3(cred: &Cred) -> Data {
-    let ws = WebSocket::new("ws://127.0.0.1:61338").unwrap();
-    let cloned_ws = ws.clone();
-    let map = Data(Arc::new(Mutex::new(BTreeMap::new())));
-    let sync_map = Arc::clone(&map);
-    let cred = cred.clone();
+fn connect(url: Arc<str>, cred: Cred, data: Data, attempt: u32) {
+    *data.link.lock().unwrap() = Link::Connecting;
+
+    let ws = match WebSocket::new(&url) {
+        Ok(ws) => ws,
+        Err(e) => {
+            console_log!("WebSocket {url}: {:?}", e);
+            return retry(url, cred, data, attempt + 1)
+        }
+    };
     ws.set_binary_type(BinaryType::Arraybuffer);
 
+    let sync_data = data.clone();
     let cb = Closure::<dyn FnMut(_)>::new(move |e: MessageEvent| {
-        console_log!("Recv: {}", &e.data().as_string().unwrap());
-        *sync_map.lock().unwrap() = serde_json::from_str(&e.data().as_string().unwrap()).unwrap()
+        let Some(text) = e.data().as_string() else { return };
+
+        match serde_json::from_str(&text) {
+            Ok(map) => *sync_data.map.lock().unwrap() = map,
+            Err(e) => console_log!("Mensagem inválida: {e}")
+        }
     });
+    ws.set_onmessage(Some(cb.as_ref().unchecked_ref()));
+    *data.onmessage.borrow_mut() = Some(cb);
 
-    let init = Closure::<dyn FnMut()>::new(move || {
+    let cloned_ws = ws.clone();
+    let sync_link = Arc::clone(&data.link);
+    let subscription = serde_json::to_string(&cred).unwrap_or_default();
+    let init = Closure::once_into_js(move || {
         console_log!("init");
-        console_log!("{:?}", cloned_ws.send_with_str(&serde_json::to_string(&cred).unwrap()));
+        *sync_link.lock().unwrap() = Link::Connected;
+
+        // Sent again on every connection, the bridge keeps no state between them
+        if let Err(e) = cloned_ws.send_with_str(&subscription) {
+            console_log!("{:?}", e);
+        }
     });
 
-    ws.set_onmessage(Some(cb.as_ref().unchecked_ref()));
-    ws.set_onopen(Some(init.as_ref().unchecked_ref()));
-    cb.forget();
-    init.forget();
+    let close = Closure::once_into_js(move || {
+        console_log!("Conexão encerrada");
+        // A socket that did open starts the backoff over
+        let attempt = if *data.link.lock().unwrap() == Link::Connected { 1 } else { attempt + 1 };
+
+        retry(url, cred, data, attempt)
+    });
+
+    ws.set_onopen(Some(init.unchecked_ref()));
+    // `error` is always followed by `close`
+    ws.set_onclose(Some(close.unchecked_ref()));
+}
 
-    map
-}
\ No newline at end of file
+#[cfg(target_arch = "wasm32")]
+fn retry(url: Arc<str>, cred: Cred, data: Data, attempt: u32) {
+    let delay = 2u32.saturating_pow(attempt.saturating_sub(1)).min(MAX_RETRY);
+    *data.link.lock().unwrap() = Link::Reconnecting { attempt, delay };
+
+    let Some(window) = web_sys::window() else { return };
+    let again = Closure::once_into_js(move || connect(url, cred, data, attempt));
+
+    if let Err(e) = window.set_timeout_with_callback_and_timeout_and_arguments_0(again.unchecked_ref(), delay as i32 * 1000) {
+        console_log!("{:?}", e);
+    }
+}
@@ -0,0 +1,227 @@
+use super::*;
+use std::{
+    sync::atomic::Ordering::Relaxed,
+    time::Instant
+};
+use eframe::egui::{
+    Align2,
+    FontId,
+    Rect
+};
+
+/// Width over height of the tiles, the same as the dashboard ones.
+const TILE_RATIO: f32 = WITDH / HEIGHT;
+const GAP: f32 = 8.;
+/// Wait before logging in again after a failure.
+const RETRY: Duration = Duration::from_secs(5);
+
+impl SipMonitor {
+    /// Full screen board: logged in from the settings, tiles as big as the screen allows,
+    /// pages taking turns when they don't fit, and nothing to click.
+    pub(super) fn wallboard(&mut self, ctx: &egui::Context) {
+        ctx.request_repaint_after(Duration::from_secs(1));
+
+        if self.conn.is_none() {
+            let cred = match (&self.settings.ami, &self.settings.replay) {
+                (Some(ami), _) => Cred { addr: ami.addr.clone(), user: ami.user.clone(), pass: ami.pass.clone() },
+                (None, Some(_)) => Cred::default(),
+                (None, None) => {
+                    egui::CentralPanel::default().show(ctx, |ui| {
+                        ui.centered_and_justified(|ui| ui.heading(format!("The wallboard logs in with the `ami` of {SETTINGS_FILE}")));
+                    });
+                    return
+                }
+            };
+            // Logs in again once the failure has been on screen for a while
+            if self.login.is_none() && self.login_error.as_ref().map_or(true, |(_, at)| at.elapsed() >= RETRY) {
+                self.login = Some(spawn_login(self.settings.clone()));
+            }
+
+            let result = match self.login.as_ref().map(Receiver::try_recv) {
+                Some(Ok(result)) => Some(result),
+                Some(Err(mpsc::TryRecvError::Disconnected)) => Some(Err(io::Error::new(io::ErrorKind::Other, "login interrompido"))),
+                Some(Err(mpsc::TryRecvError::Empty)) | None => None
+            };
+
+            match result {
+                Some(Ok(monitor)) => {
+                    self.cred = cred;
+                    self.conn = Some(monitor);
+                    self.login = None;
+                    self.login_error = None;
+                },
+                Some(Err(e)) => {
+                    eprintln!("Falha no login do wallboard: {e}");
+                    self.login = None;
+                    self.login_error = Some((e.to_string(), Instant::now()));
+                },
+                None => ()
+            }
+        }
+
+        match (&self.conn, &self.login_error) {
+            (Some(_), _) => (),
+            (None, Some((error, at))) => {
+                let wait = RETRY.saturating_sub(at.elapsed()).as_secs() + 1;
+
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    ui.centered_and_justified(|ui| ui.heading(format!("⚠ Login failed: {error}\nTrying again in {wait}s")));
+                });
+                return
+            },
+            (None, None) => {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    ui.centered_and_justified(|ui| ui.heading("Logging in…"));
+                });
+                return
+            }
+        }
+
+        let Some(monitor) = &self.conn else { return };
+        let conf = self.settings.wallboard.clone().unwrap_or_default();
+        let stale = !monitor.stats.connected.load(Relaxed);
+        let alerts = monitor.alerts.lock().unwrap().clone();
+
+        if !alerts.is_empty() {
+            egui::TopBottomPanel::top("alerts").frame(Frame::default().fill(Color32::DARK_RED).inner_margin(8.)).show(ctx, |ui| {
+                for alert in alerts {
+                    ui.label(RichText::new(format!("⚠ {}: {}", alert.rule, alert.message)).color(Color32::WHITE).size(24.));
+                }
+            });
+        }
+
+        let data = monitor.data.lock().unwrap().clone();
+        let directory = monitor.directory.lock().unwrap().clone();
+        let features = monitor.features.lock().unwrap().clone();
+        // Idle and not refusing calls
+        let available = data.iter().filter(|(exten, status)| status.state() == ExtensionState::Idle && !features.get(*exten).map_or(false, |x| x.dnd)).count();
+        let (waiting, longest) = {
+            let queues = monitor.queues.lock().unwrap();
+            (queues.values().map(|x| x.callers.len()).sum::<usize>(), queues.values().map(|x| x.longest_wait()).max().unwrap_or_default())
+        };
+        let in_progress = active_calls(&monitor.channels.lock().unwrap());
+        let theme = pick_theme(&self.themes, &self.conf.theme);
+
+        let (good, bad, info) = match self.conf.dark {
+            true => (Color32::LIGHT_GREEN, Color32::LIGHT_RED, Color32::LIGHT_BLUE),
+            false => (Color32::DARK_GREEN, Color32::DARK_RED, Color32::DARK_BLUE)
+        };
+
+        egui::TopBottomPanel::top("kpis").frame(Frame::default().inner_margin(12.)).show(ctx, |ui| {
+            let plain = ui.visuals().text_color();
+
+            // ⚠ besides the red, for those who can't tell it from the green
+            ui.columns(4, |columns| {
+                kpi(&mut columns[0], "Agents available", format!("{available}/{}", data.len()), good);
+                kpi(&mut columns[1], "Calls waiting", format!("{}{waiting}", if waiting > 0 { "⚠ " } else { "" }), if waiting > 0 { bad } else { good });
+                kpi(&mut columns[2], "Longest wait", format!("{}{}:{:02}", if longest >= 60 { "⚠ " } else { "" }, longest / 60, longest % 60), if longest >= 60 { bad } else { plain });
+                kpi(&mut columns[3], "Calls in progress", format!("{in_progress}"), info);
+            });
+        });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let area = ui.available_rect_before_wrap();
+            let (cols, per_page, size) = fit(data.len(), area.size(), conf.min_tile_width);
+            let pages = (data.len() + per_page - 1) / per_page;
+            let page = (unix_now() / conf.page_seconds.max(1)) as usize % pages.max(1);
+
+            for (idx, (exten, status)) in data.iter().skip(page * per_page).take(per_page).enumerate() {
+                let min = area.min + Vec2::new((idx % cols) as f32 * (size.x + GAP), (idx / cols) as f32 * (size.y + GAP));
+                let tile = Rect::from_min_size(min, size);
+                let painter = ui.painter_at(tile);
+                let colors = theme.colors(status.state());
+                let (fill, text) = (rgb(colors.fill), rgb(colors.text));
+                let pad = size.y * 0.08;
+
+                painter.rect_filled(tile, size.y * 0.06, if stale { fill.linear_multiply(0.35) } else { fill });
+                painter.text(tile.left_top() + Vec2::splat(pad), Align2::LEFT_TOP, exten, FontId::proportional(size.y * 0.3), text);
+
+                if let Some(name) = directory.get(exten).and_then(|x| x.display_name.as_ref()) {
+                    painter.text(tile.left_center() + Vec2::new(pad, 0.), Align2::LEFT_CENTER, name, FontId::proportional(size.y * 0.2), text);
+                }
+                if features.get(exten).map_or(false, |x| x.dnd) {
+                    painter.text(tile.right_top() + Vec2::new(-pad, pad), Align2::RIGHT_TOP, "⛔ DND", FontId::proportional(size.y * 0.16), text);
+                }
+                painter.text(tile.left_bottom() + Vec2::new(pad, -pad), Align2::LEFT_BOTTOM, format!("{} {}", status.state().icon(), status.status_text), FontId::proportional(size.y * 0.18), text);
+            }
+
+            if pages > 1 {
+                ui.painter().text(area.right_bottom() - Vec2::splat(4.), Align2::RIGHT_BOTTOM, format!("{}/{pages}", page + 1), FontId::proportional(16.), Color32::GRAY);
+            }
+        });
+    }
+}
+
+fn kpi(ui: &mut egui::Ui, label: &str, value: String, color: Color32) {
+    ui.vertical_centered(|ui| {
+        ui.label(RichText::new(value).size(48.).strong().color(color));
+        ui.label(RichText::new(label).size(18.));
+    });
+}
+
+/// Columns, tiles per page and tile size of the biggest tiles that fit `count` of them in `area`.
+/// Below `min_width` the tiles stay that wide and the rest goes to other pages.
+fn fit(count: usize, area: Vec2, min_width: f32) -> (usize, usize, Vec2) {
+    let best = |count: usize| (1..=count.max(1))
+        .map(|cols| {
+            let rows = (count + cols - 1) / cols;
+            let width = (area.x / cols as f32 - GAP).min((area.y / rows as f32 - GAP) * TILE_RATIO);
+
+            (cols, width)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or((1, min_width));
+
+    let (cols, width) = best(count);
+
+    if width >= min_width {
+        return (cols, count.max(1), Vec2::new(width, width / TILE_RATIO))
+    }
+
+    let cols = ((area.x / (min_width + GAP)) as usize).max(1);
+    let rows = ((area.y / (min_width / TILE_RATIO + GAP)) as usize).max(1);
+    let per_page = cols * rows;
+    let (cols, width) = best(per_page);
+
+    (cols, per_page, Vec2::new(width, width / TILE_RATIO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Whether `count` tiles of `size` in `cols` columns fit in `area`.
+    fn fits(count: usize, cols: usize, size: Vec2, area: Vec2) -> bool {
+        let rows = (count + cols - 1) / cols;
+
+        cols as f32 * (size.x + GAP) <= area.x + 0.01 && rows as f32 * (size.y + GAP) <= area.y + 0.01
+    }
+
+    #[test]
+    fn tiles_grow_to_fill_the_screen() {
+        let area = Vec2::new(1920., 1000.);
+
+        let (cols, per_page, size) = fit(1, area, 130.);
+        assert_eq!((cols, per_page), (1, 1));
+        assert!(fits(1, cols, size, area));
+        assert!((size.x / size.y - TILE_RATIO).abs() < 0.01);
+
+        let (cols, per_page, size) = fit(12, area, 130.);
+        assert_eq!(per_page, 12);
+        assert!(fits(12, cols, size, area));
+        assert!(size.x > 130.);
+
+        // Nothing to show still lays out one tile
+        assert_eq!(fit(0, area, 130.).1, 1);
+    }
+
+    #[test]
+    fn too_many_tiles_take_pages() {
+        let area = Vec2::new(800., 400.);
+        let (cols, per_page, size) = fit(500, area, 130.);
+
+        assert!(per_page < 500);
+        assert!(fits(per_page, cols, size, area));
+        assert!(size.x >= 130.);
+    }
+}
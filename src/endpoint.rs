@@ -0,0 +1,164 @@
+use crate::*;
+
+/// Reply of `PJSIPShowEndpoint`, one struct per kind of event in it.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct EndpointDetail {
+    pub endpoint: String,
+    pub callerid: String,
+    pub context: String,
+    pub codecs: Vec<String>,
+    pub transport: String,
+    pub device_state: String,
+    /// Channel names, as reported by Asterisk
+    pub active_channels: Vec<String>,
+    pub nat: NatSettings,
+    pub aors: Vec<AorDetail>,
+    pub auths: Vec<AuthDetail>,
+    pub contacts: Vec<ContactDetail>,
+    pub transports: Vec<TransportDetail>
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct NatSettings {
+    pub rtp_symmetric: String,
+    pub force_rport: String,
+    pub rewrite_contact: String,
+    pub direct_media: String,
+    pub ice_support: String,
+    pub media_address: String
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct AorDetail {
+    pub name: String,
+    pub max_contacts: String,
+    pub qualify_frequency: String,
+    pub contacts: String
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct AuthDetail {
+    pub name: String,
+    pub username: String,
+    pub auth_type: String
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct ContactDetail {
+    pub aor: String,
+    pub uri: String,
+    pub status: String,
+    /// Round trip in microseconds, empty if never qualified
+    pub rtt_usec: String,
+    pub user_agent: String,
+    pub via: String
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct TransportDetail {
+    pub name: String,
+    pub protocol: String,
+    pub bind: String,
+    pub external_media_address: String,
+    pub external_signaling_address: String,
+    pub local_net: String
+}
+
+impl EndpointDetail {
+    /// Builds the detail from the frames of the reply, events of other kinds are ignored.
+    pub fn parse<S: AsRef<str>>(frames: &[S]) -> Self {
+        let mut detail = Self::default();
+
+        for frame in frames {
+            let v = get_map(frame.as_ref());
+            let get = |key: &str| v.get(key).unwrap_or(&"").trim().to_owned();
+
+            match v.get("Event").copied().unwrap_or_default() {
+                "EndpointDetail" => {
+                    detail.endpoint = get("ObjectName");
+                    detail.callerid = get("Callerid");
+                    detail.context = get("Context");
+                    // `(ulaw|alaw)`
+                    detail.codecs = get("Allow").trim_matches(|x| x == '(' || x == ')').split(['|', ',']).filter(|x| !x.is_empty()).map(str::to_owned).collect();
+                    detail.transport = get("Transport");
+                    detail.device_state = get("DeviceState");
+                    detail.active_channels = get("ActiveChannels").split(',').map(str::trim).filter(|x| !x.is_empty()).map(str::to_owned).collect();
+                    detail.nat = NatSettings {
+                        rtp_symmetric: get("RtpSymmetric"),
+                        force_rport: get("ForceRport"),
+                        rewrite_contact: get("RewriteContact"),
+                        direct_media: get("DirectMedia"),
+                        ice_support: get("IceSupport"),
+                        media_address: get("MediaAddress")
+                    };
+                },
+                "AorDetail" => detail.aors.push(AorDetail {
+                    name: get("ObjectName"),
+                    max_contacts: get("MaxContacts"),
+                    qualify_frequency: get("QualifyFrequency"),
+                    contacts: get("Contacts")
+                }),
+                "AuthDetail" => detail.auths.push(AuthDetail {
+                    name: get("ObjectName"),
+                    username: get("Username"),
+                    auth_type: get("AuthType")
+                }),
+                "ContactStatusDetail" => detail.contacts.push(ContactDetail {
+                    aor: get("AOR"),
+                    uri: get("URI"),
+                    status: get("Status"),
+                    rtt_usec: get("RoundtripUsec"),
+                    user_agent: get("UserAgent"),
+                    via: get("ViaAddress")
+                }),
+                "TransportDetail" => detail.transports.push(TransportDetail {
+                    name: get("ObjectName"),
+                    protocol: get("Protocol"),
+                    bind: get("Bind"),
+                    external_media_address: get("ExternalMediaAddress"),
+                    external_signaling_address: get("ExternalSignalingAddress"),
+                    local_net: get("LocalNet")
+                }),
+                _ => ()
+            }
+        }
+
+        detail
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_show_endpoint_reply() {
+        let detail = EndpointDetail::parse(&[
+            "Response: Success\r\nEventList: start\r\nMessage: Following are Events for each object associated with the Endpoint\r\n",
+            "Event: EndpointDetail\r\nObjectName: 2003\r\nCallerid: \"Maria\" <2003>\r\nContext: from-internal\r\nAllow: (ulaw|alaw|g722)\r\nTransport: transport-udp\r\nDeviceState: In use\r\nActiveChannels: PJSIP/2003-00000001, PJSIP/2003-00000002\r\nRtpSymmetric: true\r\nForceRport: true\r\nRewriteContact: true\r\nDirectMedia: false\r\nIceSupport: false\r\nMediaAddress: \r\n",
+            "Event: AorDetail\r\nObjectName: 2003\r\nMaxContacts: 1\r\nQualifyFrequency: 60\r\nContacts: 2003/sip:2003@10.0.0.5:5060\r\n",
+            "Event: AuthDetail\r\nObjectName: 2003-auth\r\nUsername: 2003\r\nAuthType: userpass\r\n",
+            "Event: TransportDetail\r\nObjectName: transport-udp\r\nProtocol: udp\r\nBind: 0.0.0.0:5060\r\nExternalMediaAddress: 200.1.2.3\r\nExternalSignalingAddress: 200.1.2.3\r\nLocalNet: 10.0.0.0/8\r\n",
+            "Event: ContactStatusDetail\r\nAOR: 2003\r\nURI: sip:2003@10.0.0.5:5060\r\nStatus: Reachable\r\nRoundtripUsec: 2310\r\nUserAgent: Yealink SIP-T46S\r\nViaAddress: 10.0.0.5:5060\r\n",
+            "Event: EndpointDetailComplete\r\nEventList: Complete\r\nListItems: 5\r\n"
+        ]);
+
+        assert_eq!(detail.endpoint, "2003");
+        assert_eq!(detail.callerid, "\"Maria\" <2003>");
+        assert_eq!(detail.codecs, ["ulaw", "alaw", "g722"]);
+        assert_eq!(detail.active_channels, ["PJSIP/2003-00000001", "PJSIP/2003-00000002"]);
+        assert_eq!((&detail.nat.rtp_symmetric[..], &detail.nat.direct_media[..], &detail.nat.media_address[..]), ("true", "false", ""));
+        assert_eq!((detail.aors.len(), &detail.aors[0].qualify_frequency[..]), (1, "60"));
+        assert_eq!((&detail.auths[0].name[..], &detail.auths[0].auth_type[..]), ("2003-auth", "userpass"));
+        assert_eq!((&detail.transports[0].bind[..], &detail.transports[0].local_net[..]), ("0.0.0.0:5060", "10.0.0.0/8"));
+        assert_eq!((&detail.contacts[0].status[..], &detail.contacts[0].rtt_usec[..], &detail.contacts[0].via[..]), ("Reachable", "2310", "10.0.0.5:5060"));
+    }
+
+    #[test]
+    fn nothing_allowed_gives_no_codecs() {
+        let detail = EndpointDetail::parse(&["Event: EndpointDetail\r\nObjectName: 2004\r\nAllow: ()\r\nActiveChannels: \r\n"]);
+
+        assert!(detail.codecs.is_empty() && detail.active_channels.is_empty());
+        assert!(detail.aors.is_empty() && detail.contacts.is_empty());
+    }
+}
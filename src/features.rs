@@ -0,0 +1,136 @@
+use crate::*;
+
+/// DND and forwarding of the extensions, as FreePBX keeps them in the AstDB.
+pub type Features = Arc<Mutex<BTreeMap<String, CallFeatures>>>;
+
+/// An AstDB family FreePBX keys by extension: `DND/2003`, `CF/2003`, ...
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Feature {
+    Dnd,
+    /// Unconditional forward
+    Cf,
+    /// Forward on busy
+    Cfb,
+    /// Forward on no answer / unavailable
+    Cfu
+}
+
+impl Feature {
+    pub const ALL: [Self; 4] = [Self::Dnd, Self::Cf, Self::Cfb, Self::Cfu];
+
+    pub fn family(&self) -> &'static str {
+        match self {
+            Self::Dnd => "DND",
+            Self::Cf => "CF",
+            Self::Cfb => "CFB",
+            Self::Cfu => "CFU"
+        }
+    }
+
+    fn from_family(family: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|x| x.family() == family)
+    }
+}
+
+impl Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Dnd => write!(f, "DND"),
+            Self::Cf => write!(f, "Forward"),
+            Self::Cfb => write!(f, "Forward on busy"),
+            Self::Cfu => write!(f, "Forward on no answer")
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CallFeatures {
+    pub dnd: bool,
+    /// Forward targets
+    pub cf: Option<String>,
+    pub cfb: Option<String>,
+    pub cfu: Option<String>
+}
+
+impl CallFeatures {
+    pub fn get(&self, feature: Feature) -> Option<&str> {
+        match feature {
+            Feature::Dnd => self.dnd.then_some("YES"),
+            Feature::Cf => self.cf.as_deref(),
+            Feature::Cfb => self.cfb.as_deref(),
+            Feature::Cfu => self.cfu.as_deref()
+        }
+    }
+
+    /// `None` clears the feature. FreePBX stores `YES` for DND, any value turns it on here.
+    pub fn set(&mut self, feature: Feature, val: Option<&str>) {
+        let val = val.map(str::trim).filter(|x| !x.is_empty()).map(str::to_owned);
+
+        match feature {
+            Feature::Dnd => self.dnd = val.is_some(),
+            Feature::Cf => self.cf = val,
+            Feature::Cfb => self.cfb = val,
+            Feature::Cfu => self.cfu = val
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        Feature::ALL.into_iter().all(|x| self.get(x).is_none())
+    }
+}
+
+/// Extension and value of a `DBGetResponse` or `DBGetTreeResponse` of a family in `Feature`.
+pub fn db_entry(val: &str) -> Option<(Feature, String, String)> {
+    let v = get_map(val);
+    let family = v.get("Family").copied().unwrap_or_default();
+    let key = v.get("Key")?;
+    // The tree gives the whole path, `/DND/2003`
+    let path = if key.starts_with('/') { key.to_string() } else { format!("/{family}/{key}") };
+    let (family, exten) = path.trim_start_matches('/').split_once('/')?;
+
+    Some((Feature::from_family(family)?, exten.to_owned(), v.get("Val").unwrap_or(&"").to_string()))
+}
+
+/// Updates `features` from `DBGetResponse` and `DBGetTreeResponse` events; other frames are ignored.
+///
+/// Only sets what the AstDB has, clearing the keys that are gone is up to whoever asked for the tree.
+pub fn treat_features(val: &str, features: &Features) {
+    match val.as_bytes() {
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'D', b'B', b'G', b'e', b't', b'R', b'e', b's', b'p', b'o', b'n', b's', b'e', b'\r', ..] |
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'D', b'B', b'G', b'e', b't', b'T', b'r', b'e', b'e', b'R', b'e', b's', b'p', b'o', b'n', b's', b'e', b'\r', ..] => {
+            let Some((feature, exten, value)) = db_entry(val) else { return };
+
+            features.lock().unwrap().entry(exten).or_default().set(feature, Some(&value));
+        },
+        _ => ()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn db_entry_reads_get_and_tree_replies() {
+        assert_eq!(db_entry("Event: DBGetResponse\r\nFamily: DND\r\nKey: 2003\r\nVal: YES\r\n"), Some((Feature::Dnd, "2003".to_owned(), "YES".to_owned())));
+        assert_eq!(db_entry("Event: DBGetTreeResponse\r\nKey: /CFB/2004\r\nVal: 5551234\r\n"), Some((Feature::Cfb, "2004".to_owned(), "5551234".to_owned())));
+        assert_eq!(db_entry("Event: DBGetTreeResponse\r\nKey: /AMPUSER/2003/cidname\r\nVal: Maria\r\n"), None);
+        assert_eq!(db_entry("Event: DBGetResponse\r\nFamily: DND\r\n"), None);
+    }
+
+    #[test]
+    fn treat_features_sets_what_the_astdb_has() {
+        let features = Features::default();
+
+        treat_features("Event: DBGetTreeResponse\r\nKey: /DND/2003\r\nVal: YES\r\n", &features);
+        treat_features("Event: DBGetResponse\r\nFamily: CF\r\nKey: 2003\r\nVal: 5551234\r\n", &features);
+        treat_features("Event: DBGetResponse\r\nFamily: CFU\r\nKey: 2003\r\nVal:  \r\n", &features);
+        // Not a reply to the AstDB actions
+        treat_features("Event: UserEvent\r\nFamily: CFB\r\nKey: 2003\r\nVal: 1\r\n", &features);
+
+        let features = features.lock().unwrap();
+        assert_eq!(features["2003"], CallFeatures { dnd: true, cf: Some("5551234".to_owned()), cfb: None, cfu: None });
+        assert!(!features["2003"].is_empty());
+    }
+}
@@ -0,0 +1,63 @@
+use crate::*;
+
+/// Raw frames kept for the AMI inspector.
+pub const FRAMES_SIZE: usize = 2_000;
+
+pub type Frames = Arc<Mutex<FrameLog>>;
+
+#[derive(Debug, Clone)]
+pub struct RawFrame {
+    pub seq: u64,
+    /// Unix timestamp
+    pub at: u64,
+    pub dir: Direction,
+    pub text: String
+}
+
+impl RawFrame {
+    /// Value of the `Event`, `Response` or `Action` header.
+    pub fn name(&self) -> &str {
+        self.text.lines()
+            .find_map(|x| x.strip_prefix("Event: ").or(x.strip_prefix("Response: ")).or(x.strip_prefix("Action: ")))
+            .unwrap_or_default()
+            .trim()
+    }
+
+    pub fn action_id(&self) -> Option<u64> {
+        self.headers().into_iter().find(|(k, _)| *k == "ActionID").and_then(|(_, v)| v.parse().ok())
+    }
+
+    /// Headers in the order they were sent. `get_map` would lose the repeated ones.
+    pub fn headers(&self) -> Vec<(&str, &str)> {
+        self.text.lines()
+            .filter_map(|x| x.split_once(": ").or_else(|| x.split_once(':')))
+            .map(|(k, v)| (k.trim(), v.trim()))
+            .collect()
+    }
+}
+
+/// Ring buffer of the latest frames read from and written to the PBX.
+#[derive(Debug, Default)]
+pub struct FrameLog {
+    next_seq: u64,
+    entries: VecDeque<RawFrame>
+}
+
+impl FrameLog {
+    pub fn push(&mut self, dir: Direction, text: &str) {
+        self.next_seq += 1;
+        self.entries.push_back(RawFrame { seq: self.next_seq, at: unix_now(), dir, text: text.to_owned() });
+
+        if self.entries.len() > FRAMES_SIZE {
+            self.entries.pop_front();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &RawFrame> {
+        self.entries.iter()
+    }
+}
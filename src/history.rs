@@ -0,0 +1,44 @@
+use crate::*;
+
+/// Status changes kept for `/api/history`.
+pub const HISTORY_SIZE: usize = 10_000;
+
+/// Ring buffer of the latest status changes, each one numbered in sequence.
+#[derive(Debug, Default)]
+pub struct History {
+    next_id: u64,
+    entries: VecDeque<StatusChange>
+}
+
+impl History {
+    /// Numbers the change and stores it.
+    pub fn push(&mut self, mut change: StatusChange) -> StatusChange {
+        self.next_id += 1;
+        change.id = self.next_id;
+        self.entries.push_back(change.clone());
+
+        if self.entries.len() > HISTORY_SIZE {
+            self.entries.pop_front();
+        }
+
+        change
+    }
+
+    pub fn last_id(&self) -> u64 {
+        self.next_id
+    }
+
+    /// Whether every change after `id` is still kept.
+    pub fn has(&self, id: u64) -> bool {
+        id <= self.next_id && self.entries.front().map_or(true, |x| x.id <= id + 1)
+    }
+
+    /// Changes numbered after `id`, oldest first.
+    pub fn after(&self, id: u64) -> impl Iterator<Item = &StatusChange> {
+        self.entries.iter().skip_while(move |x| x.id <= id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &StatusChange> {
+        self.entries.iter()
+    }
+}
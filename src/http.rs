@@ -0,0 +1,188 @@
+use crate::*;
+use std::net::{
+    TcpListener,
+    TcpStream,
+    ToSocketAddrs
+};
+
+pub type Handler = Arc<dyn Fn(&Request) -> Response + Send + Sync>;
+/// Writes a body of unknown length after the head, until it returns.
+pub type Stream = Box<dyn FnOnce(&mut TcpStream) -> Result<()> + Send>;
+
+#[derive(Debug, Default, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    /// Header names are lowercased
+    pub headers: HashMap<String, String>
+}
+
+impl Request {
+    fn read(stream: &mut impl BufRead) -> Option<Self> {
+        let mut first = String::new();
+        stream.read_line(&mut first).ok()?;
+
+        let mut parts = first.split_whitespace();
+        let method = parts.next()?.to_owned();
+        let target = parts.next()?;
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let mut headers = HashMap::new();
+        let mut line = String::new();
+
+        loop {
+            line.clear();
+            if stream.read_line(&mut line).ok()? <= 2 {
+                break
+            }
+
+            if let Some((key, value)) = line.split_once(':') {
+                headers.insert(key.trim().to_lowercase(), value.trim().to_owned());
+            }
+        }
+
+        Some(Self {
+            method,
+            path: decode(path),
+            query: query.split('&').filter_map(|x| x.split_once('=')).map(|(k, v)| (decode(k), decode(v))).collect(),
+            headers
+        })
+    }
+
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.query.get(key).map(|x| &x[..])
+    }
+}
+
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    pub stream: Option<Stream>
+}
+
+impl Response {
+    pub fn new<B: Into<Vec<u8>>>(status: u16, content_type: &str, body: B) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type".to_owned(), content_type.to_owned())],
+            body: body.into(),
+            stream: None
+        }
+    }
+
+    pub fn stream<F>(content_type: &str, stream: F) -> Self
+        where F: FnOnce(&mut TcpStream) -> Result<()> + Send + 'static
+    {
+        Self {
+            stream: Some(Box::new(stream)),
+            ..Self::new(200, content_type, "")
+        }
+    }
+
+    pub fn text<B: Into<Vec<u8>>>(body: B) -> Self {
+        Self::new(200, "text/plain; charset=utf-8", body)
+    }
+
+    pub fn json<T: Serialize + ?Sized>(val: &T) -> Self {
+        Self::new(200, "application/json", serde_json::to_vec(val).unwrap_or_default())
+    }
+
+    pub fn not_found() -> Self {
+        Self::new(404, "text/plain; charset=utf-8", "Not Found")
+    }
+
+    pub fn header(mut self, key: &str, value: &str) -> Self {
+        self.headers.push((key.to_owned(), value.to_owned()));
+        self
+    }
+
+    fn write(self, stream: &mut TcpStream) -> Result<()> {
+        let mut head = format!("HTTP/1.1 {} {}\r\n", self.status, reason(self.status));
+
+        // Upgrades set their own
+        if !self.headers.iter().any(|(key, _)| key.eq_ignore_ascii_case("Connection")) {
+            head += "Connection: close\r\n";
+        }
+
+        if self.stream.is_none() {
+            head += &format!("Content-Length: {}\r\n", self.body.len());
+        }
+
+        for (key, value) in &self.headers {
+            head += &format!("{key}: {value}\r\n");
+        }
+        head += "\r\n";
+
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(&self.body)?;
+        stream.flush()?;
+
+        match self.stream {
+            Some(body) => body(stream),
+            None => Ok(())
+        }
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        101 => "Switching Protocols",
+        200 => "OK",
+        304 => "Not Modified",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        503 => "Service Unavailable",
+        _ => ""
+    }
+}
+
+/// Percent-decodes a path or query component.
+fn decode(val: &str) -> String {
+    let mut out = Vec::with_capacity(val.len());
+    let mut bytes = val.bytes();
+
+    while let Some(byte) = bytes.next() {
+        match byte {
+            b'+' => out.push(b' '),
+            b'%' => {
+                let hex = [bytes.next().unwrap_or_default(), bytes.next().unwrap_or_default()];
+                out.push(std::str::from_utf8(&hex).ok().and_then(|x| u8::from_str_radix(x, 16).ok()).unwrap_or_default());
+            },
+            x => out.push(x)
+        }
+    }
+
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Serves `handler` on `addr` from a background thread, one thread per connection.
+pub fn serve<A: ToSocketAddrs>(addr: A, handler: Handler) -> Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    thread::spawn(move || accept(listener, handler));
+
+    Ok(())
+}
+
+/// Same as `serve`, blocking the calling thread.
+pub fn listen<A: ToSocketAddrs>(addr: A, handler: Handler) -> Result<()> {
+    accept(TcpListener::bind(addr)?, handler);
+
+    Ok(())
+}
+
+fn accept(listener: TcpListener, handler: Handler) {
+    for stream in listener.incoming().filter_map(|x| x.ok()) {
+        let handler = Arc::clone(&handler);
+        thread::spawn(move || handle(stream, &handler));
+    }
+}
+
+fn handle(mut stream: TcpStream, handler: &Handler) {
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(10)));
+    let Some(req) = Request::read(&mut io::BufReader::new(&mut stream)) else { return };
+
+    let _ = handler(&req).write(&mut stream);
+}
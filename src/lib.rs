@@ -1,14 +1,80 @@
+/// `eprintln!`, unless the terminal monitor owns the screen, see `set_log`.
+macro_rules! elog {
+    ($($arg:tt)*) => ($crate::log_line(format!($($arg)*)))
+}
+
 mod ami;
+mod api;
+mod capture;
+mod channels;
+mod conference;
+mod directory;
 mod eframealt;
+mod endpoint;
+mod features;
+mod frames;
+mod history;
+mod http;
+mod metrics;
+mod monitor;
+mod notify;
+mod parking;
+mod presence;
+mod queue;
+mod registration;
+mod rules;
+mod settings;
+mod sse;
+mod stream;
+mod theme;
+mod trunk;
+#[cfg(not(target_arch = "wasm32"))]
+mod assets;
+#[cfg(not(target_arch = "wasm32"))]
+mod tui;
+#[cfg(not(target_arch = "wasm32"))]
+mod ws;
 
 pub use self::ami::*;
+pub use self::api::*;
+pub use self::capture::*;
+pub use self::channels::*;
+pub use self::conference::*;
+pub use self::directory::*;
 pub use self::eframealt::*;
+pub use self::endpoint::*;
+pub use self::features::*;
+pub use self::frames::*;
+pub use self::history::*;
+pub use self::http::*;
+pub use self::metrics::*;
+pub use self::monitor::*;
+pub use self::notify::*;
+pub use self::parking::*;
+pub use self::presence::*;
+pub use self::queue::*;
+pub use self::registration::*;
+pub use self::rules::*;
+pub use self::settings::*;
+pub use self::sse::*;
+pub use self::stream::*;
+pub use self::theme::*;
+pub use self::trunk::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::assets::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::tui::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::ws::*;
 
 use std::{
-    process::Command,
     thread,
     sync::{
-        mpsc,
+        mpsc::{
+            self,
+            Sender,
+            Receiver
+        },
         Arc,
         Mutex
     },
@@ -16,126 +82,200 @@ use std::{
         self,
         Display
     },
-    time::Duration,
+    time::{
+        Duration,
+        SystemTime,
+        UNIX_EPOCH
+    },
     collections::{
         BTreeMap,
-        HashMap
+        HashMap,
+        HashSet,
+        VecDeque
     },
     io::{
         self,
         prelude::*,
         Result
     },
-    cmp::Ordering
+    cmp::Ordering,
+    mem
 };
 use serde::*;
 
 const WITDH: f32 = 130.;
 const HEIGHT: f32 = 70.;
 
+/// Events the monitors consume; everything else is filtered out by Asterisk.
+pub const STATUS_EVENTS: &[&str] = &["ExtensionStatus"];
+/// How many `ExtensionState` requests the bootstrap keeps in flight, unless the settings say otherwise.
+pub const BOOTSTRAP_WINDOW: usize = 64;
+
 type AllData = Arc<Mutex<BTreeMap<String, SipStatus>>>;
 
-pub fn ami_monitoring() -> Result<()> {
+/// The `ami` settings, or the credentials asked on the terminal. Prompts go to stderr, keeping stdout for the output.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn ami_settings(settings: &Settings) -> AmiSettings {
+    if let Some(conf) = &settings.ami {
+        return conf.clone()
+    }
+
     let mut buf = [0u8; 1024];
 
-    print!("IP: ");
-    io::stdout().flush().unwrap();
+    eprint!("IP: ");
+    let size = io::stdin().read(&mut buf).unwrap();
+    let addr = String::from_utf8_lossy(&buf[..size]).trim().to_owned();
+
+    eprint!("User: ");
     let size = io::stdin().read(&mut buf).unwrap();
-    let ip = String::from_utf8_lossy(&buf[..size]).trim().parse().unwrap();
+    let user = String::from_utf8_lossy(&buf[..size]).trim().to_owned();
 
-    print!("Pass: ");
-    io::stdout().flush().unwrap();
+    eprint!("Pass: ");
     let size = io::stdin().read(&mut buf).unwrap();
     let pass = String::from_utf8_lossy(&buf[..size]).trim().to_owned();
 
-    let ami = AmiConnect::new("Monitor".to_owned(), pass, ip, 5038);
-    let mut ami = Ami::new(ami)?;
-    let (send, recv) = mpsc::channel();
-    let map = Arc::new(Mutex::new(BTreeMap::new()));
-    let sync_map = Arc::clone(&map);
+    AmiSettings { addr, user, pass, port: 5038, record: None }
+}
 
-    ami.init_treat(move |val| {
-        // println!("{val}\n");
-        match val.as_bytes() {
-            [b'R', b'e', b's', b'p', b'o', b'n', b's', b'e', b':', b' ', b'S', b'u', b'c', b'c', b'e', b's', b's', ..] => {
-                let msg = process(&val);
+/// Plays `settings.replay` when set, otherwise connects to the PBX.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn start_monitor(settings: &Settings) -> Result<Arc<Monitor>> {
+    let monitor = match &settings.replay {
+        Some(replay) => Monitor::replay(replay, settings.rules.clone(), settings.bootstrap_window)?,
+        None => Monitor::start(&ami_settings(settings), settings.rules.clone(), settings.bootstrap_window)?
+    };
+    monitor.configure(settings);
 
-                send.send(msg).unwrap()
-            },
-            [b'R', b'e', b's', b'p', b'o', b'n', b's', b'e', b':', b' ', ..] => {
-                println!("{val}");
-                send.send(Message::Unknown).unwrap()
-            },
-            [b'E', b'v', b'e', b'n', b't', b':', b' ', b'A', b'o', b'r', b'L', b'i', b's', b't', b'C', b'o', b'm', b'p', b'l', b'e', b't', b'e', ..] => send.send(Message::Complete).unwrap(),
-            [b'E', b'v', b'e', b'n', b't', b':', b' ', b'A', b'o', b'r', b'L', b'i', b's', b't', ..] => {
-                let msg = contact(&val);
+    Ok(monitor)
+}
 
-                send.send(msg).unwrap()
-            },
-            [b'E', b'v', b'e', b'n', b't', b':', b' ', b'E', b'x', b't', b'e', b'n', b's', b'i', b'o', b'n', b'S', b't', b'a', b't', b'u', b's', ..] => {
-                let Message::Sip(sip, status) = process(&val) else { return };
-                sync_map.lock().unwrap()
-                    .entry(Contact::from_name(sip))
-                    .and_modify(|x| *x = status);
+/// Terminal monitor.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn ami_monitoring(settings: &Settings) -> Result<()> {
+    let monitor = start_monitor(settings)?;
 
-                send.send(Message::Updated).unwrap()
-            },
-            _ => ()
-        }
-    })?;
+    run_console(&monitor, settings)
+}
 
-    ami.pjsip_show_aors().unwrap();
-    let mut contacts = Vec::new();
+/// Prints every status change to stdout, one record per line.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn ami_stream(settings: &Settings, format: Format) -> Result<()> {
+    let monitor = start_monitor(settings)?;
 
-    if let Message::Start = recv.recv().unwrap() {
-        loop {
-            let msg = recv.recv().unwrap();
+    run_stream(&monitor, format)
+}
 
-            match msg {
-                Message::Complete => break,
-                Message::Contact { contact, name } => {
-                    contacts.push(Contact { contact, name });
+/// Routes a raw AMI frame: replies and list events go to `send`, status events update `data` in place.
+pub fn treat<K>(val: &str, send: &Sender<Message>, data: &Mutex<BTreeMap<K, SipStatus>>)
+    where K: Ord + From<String>
+{
+    // println!("{val}\n");
+    // The receiving side may be gone once bootstrap is over, so send errors are ignored
+    let _ = match val.as_bytes() {
+        [b'R', b'e', b's', b'p', b'o', b'n', b's', b'e', b':', b' ', b'S', b'u', b'c', b'c', b'e', b's', b's', ..] => {
+            let msg = process(val);
+
+            send.send(Message::Reply(action_id(val), Box::new(msg)))
+        },
+        [b'R', b'e', b's', b'p', b'o', b'n', b's', b'e', b':', b' ', ..] => {
+            elog!("{val}");
+            send.send(Message::Reply(action_id(val), Box::new(Message::Unknown)))
+        },
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'A', b'o', b'r', b'L', b'i', b's', b't', b'C', b'o', b'm', b'p', b'l', b'e', b't', b'e', ..] => send.send(Message::Complete),
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'A', b'o', b'r', b'L', b'i', b's', b't', ..] => {
+            let msg = contact(val);
+
+            send.send(msg)
+        },
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'E', b'x', b't', b'e', b'n', b's', b'i', b'o', b'n', b'S', b't', b'a', b't', b'u', b's', ..] => {
+            let Message::Sip(sip, mut status) = process(val) else { return };
+            let mut data = data.lock().unwrap();
+            let Some(entry) = data.get_mut(&K::from(sip.clone())) else { return };
+
+            // The state of the presence comes from its own events
+            if let (Some(new), Some(old)) = (&mut status.presence, &entry.presence) {
+                if new.provider == old.provider {
+                    *new = old.clone();
                 }
-                _ => ()
             }
-        }
-    }
+            let old = mem::replace(entry, status.clone());
+
+            send.send(Message::Updated(StatusChange { id: 0, pbx: String::new(), exten: sip, old, new: status, at: unix_now() }))
+        },
+        _ => Ok(())
+    };
+}
 
-    for contact in contacts {
-        ami.extension_state(&contact.name, "ext-local").unwrap();
+/// Lists the AORs and queries the state of each one, keeping up to `window` `ExtensionState` requests in flight.
+pub fn bootstrap(ami: &mut Ami, recv: &Receiver<Message>, window: usize) -> Result<Vec<(Contact, SipStatus)>> {
+    let aors = ami.pjsip_show_aors()?;
+    let mut contacts = VecDeque::new();
 
-        let Message::Sip(_, status) = recv.recv().unwrap() else { panic!() };
-        map.lock().unwrap().insert(contact, status);
+    loop {
+        match recv.recv().map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))? {
+            Message::Reply(id, msg) if id == aors => match *msg {
+                Message::Start => (),
+                _ => break
+            },
+            Message::Contact { contact, name } => contacts.push_back(Contact { contact, name, ..Default::default() }),
+            Message::Complete => break,
+            _ => ()
+        }
     }
 
-    Command::new("cmd").args(["/C", "cls"]).status().unwrap();
-    println!("{}", Some(map.lock().unwrap().iter().map(|(k, v)| format!("{k} = {v}")).collect::<Vec<_>>()).map(|mut x| { x.reverse(); x }).unwrap().join("\r\n"));
+    let mut pending = HashMap::new();
+    let mut states = Vec::with_capacity(contacts.len());
 
-    loop {
-        recv.recv().unwrap();
-        Command::new("cmd").args(["/C", "cls"]).status().unwrap();
-        println!("{}", Some(map.lock().unwrap().iter().map(|(k, v)| format!("{k} = {v}")).collect::<Vec<_>>()).map(|mut x| { x.reverse(); x }).unwrap().join("\r\n"));
+    while !contacts.is_empty() || !pending.is_empty() {
+        while pending.len() < window.max(1) {
+            let Some(contact) = contacts.pop_front() else { break };
+            let id = ami.extension_state(&contact.name, "ext-local")?;
+            pending.insert(id, contact);
+        }
 
-        thread::sleep(Duration::from_millis(1000 / 60));
+        let Message::Reply(id, msg) = recv.recv().map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))? else { continue };
+        let Some(contact) = pending.remove(&id) else { continue };
+
+        match *msg {
+            Message::Sip(_, status) => states.push((contact, status)),
+            _ => states.push((contact, SipStatus { status: -1, status_text: "Unknown".to_owned(), presence: None }))
+        }
     }
-}
 
+    Ok(states)
+}
 
-#[derive(Debug, Eq, Hash, Deserialize, Serialize)]
+#[derive(Debug, Default, Clone, Eq, Hash, Deserialize, Serialize)]
 pub struct Contact {
+    /// Extension
     pub name: String,
-    pub contact: String
+    pub contact: String,
+    /// From the directory file or the caller ID of the endpoint
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub department: Option<String>,
+    #[serde(default)]
+    pub location: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>
 }
 
 impl Contact {
     pub fn from_name<S: Into<String>>(name: S) -> Self {
         Self {
-            contact: Default::default(),
-            name: name.into()
+            name: name.into(),
+            ..Default::default()
         }
     }
 }
 
+impl From<String> for Contact {
+    fn from(name: String) -> Self {
+        Self::from_name(name)
+    }
+}
+
 impl PartialOrd for Contact {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         self.name.partial_cmp(&other.name)
@@ -172,10 +312,52 @@ impl Display for Contact {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SipStatus {
     pub status: i8,
-    pub status_text: String
+    pub status_text: String,
+    /// `None` when the hint has no presence provider
+    #[serde(default)]
+    pub presence: Option<Presence>
+}
+
+impl SipStatus {
+    pub fn state(&self) -> ExtensionState {
+        ExtensionState::from(self.status)
+    }
+}
+
+/// Typed `Status` of `ExtensionStatus`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtensionState {
+    Removed,
+    Unknown,
+    Idle,
+    InUse,
+    Busy,
+    Unavailable,
+    Ringing,
+    InUseRinging,
+    OnHold,
+    InUseOnHold
+}
+
+impl From<i8> for ExtensionState {
+    fn from(status: i8) -> Self {
+        match status {
+            -2 => Self::Removed,
+            0 => Self::Idle,
+            1 => Self::InUse,
+            2 => Self::Busy,
+            4 => Self::Unavailable,
+            8 => Self::Ringing,
+            9 => Self::InUseRinging,
+            16 => Self::OnHold,
+            17 => Self::InUseOnHold,
+            _ => Self::Unknown
+        }
+    }
 }
 
 impl Display for SipStatus {
@@ -184,15 +366,30 @@ impl Display for SipStatus {
     }
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StatusChange {
+    /// Sequence number in the monitor `History`, filled in by `Monitor`
+    pub id: u64,
+    /// Address of the PBX, filled in by `Monitor`
+    pub pbx: String,
+    pub exten: String,
+    pub old: SipStatus,
+    pub new: SipStatus,
+    /// Unix timestamp of the change
+    pub at: u64
+}
+
 pub enum Message {
     Sip(String, SipStatus),
+    /// A `Response` frame, tagged with its `ActionID`
+    Reply(u64, Box<Message>),
     Contact {
         contact: String,
         name: String
     },
     Start,
     Complete,
-    Updated,
+    Updated(StatusChange),
     Unknown
 }
 
@@ -210,11 +407,61 @@ pub fn get_map(val: &str) -> HashMap<&str, &str> {
         .collect::<HashMap<_, _>>()
 }
 
+pub fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|x| x.as_secs()).unwrap_or_default()
+}
+
+/// Formats a unix timestamp as RFC 3339 in UTC, e.g. `2023-04-20T13:45:00Z`.
+pub fn rfc3339(secs: u64) -> String {
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+    // Civil from days, after Howard Hinnant
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+
+    format!("{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z", rem / 3_600, rem % 3_600 / 60, rem % 60)
+}
+
+/// Where the `elog!` lines go instead of stderr.
+static LOG: Mutex<Option<Sender<String>>> = Mutex::new(None);
+
+/// Sends the `elog!` lines to `log`, or back to stderr with `None`.
+pub fn set_log(log: Option<Sender<String>>) {
+    *LOG.lock().unwrap() = log;
+}
+
+#[doc(hidden)]
+pub fn log_line(line: String) {
+    let line = match &*LOG.lock().unwrap() {
+        Some(log) => match log.send(line) {
+            Ok(()) => return,
+            Err(mpsc::SendError(line)) => line
+        },
+        None => line
+    };
+
+    eprintln!("{line}");
+}
+
+pub fn action_id(val: &str) -> u64 {
+    get_map(val).get("ActionID").and_then(|x| x.parse().ok()).unwrap_or_default()
+}
+
 pub fn sip_status(val: &str) -> Message {
     let v = get_map(val);
 
     if v.contains_key("Exten") && v.contains_key("Status") && v.contains_key("StatusText") {
-        Message::Sip(v.get("Exten").unwrap().to_string(), SipStatus { status: v.get("Status").unwrap().parse().unwrap_or_default(), status_text: v.get("StatusText").unwrap().to_string() })
+        Message::Sip(v.get("Exten").unwrap().to_string(), SipStatus {
+            status: v.get("Status").unwrap().parse().unwrap_or_default(),
+            status_text: v.get("StatusText").unwrap().to_string(),
+            presence: v.get("Hint").and_then(|x| Presence::from_hint(x))
+        })
     } else { Message::Unknown }
 }
 
@@ -224,4 +471,36 @@ pub fn contact(val: &str) -> Message {
     if v.contains_key("ObjectName") && v.contains_key("Contacts") {
         Message::Contact{ name: v.get("ObjectName").unwrap().parse().unwrap_or_default(), contact: v.get("Contacts").unwrap().to_string() }
     } else { Message::Unknown }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_lines_go_where_set() {
+        let (send, logs) = mpsc::channel();
+
+        set_log(Some(send));
+        elog!("Conexão com {} perdida", "10.0.0.1");
+        set_log(None);
+
+        // Other tests may log meanwhile
+        assert!(logs.iter().any(|x| x == "Conexão com 10.0.0.1 perdida"));
+    }
+
+    #[test]
+    fn rfc3339_formats_utc() {
+        assert_eq!(rfc3339(0), "1970-01-01T00:00:00Z");
+        assert_eq!(rfc3339(1_704_067_199), "2023-12-31T23:59:59Z");
+    }
+
+    #[test]
+    fn rfc3339_knows_leap_years() {
+        assert_eq!(rfc3339(68_169_600), "1972-02-29T00:00:00Z");
+        assert_eq!(rfc3339(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(rfc3339(1_709_208_000), "2024-02-29T12:00:00Z");
+        // 2100 is not one
+        assert_eq!(rfc3339(4_107_542_399), "2100-02-28T23:59:59Z");
+        assert_eq!(rfc3339(4_107_542_400), "2100-03-01T00:00:00Z");
+    }
+}
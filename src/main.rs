@@ -2,306 +2,168 @@
 
 use sip_monitor::*;
 #[cfg(windows)]
-use websocket::{sync::Client, OwnedMessage};
+use std::process::Command;
 use std::{
-    process::Command,
     thread,
-    path::Path,
-    net::{Ipv4Addr, TcpStream},
+    net::Ipv4Addr,
     sync::{
         mpsc,
         Arc,
         Mutex
     },
     time::Duration,
-    collections::{
-        BTreeMap,
-        HashMap
-    },
+    collections::BTreeMap,
     io::{
         self,
-        prelude::*,
         Result as IoResult
     }
 };
-#[cfg(windows)]
-use websocket::sync::Server;
-#[cfg(windows)]
-use ghostemp::{
-    Urls,
-    ClientHTTP,
-    prelude::*
-};
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
 fn main() {
-    #[cfg(windows)]
-    if eframe().is_none() {
-        web().unwrap();
+    let args = std::env::args().collect::<Vec<_>>();
+    let flag = |name: &str| args.windows(2).find(|x| x[0] == name).map(|x| &x[1][..]);
+    let mut settings = Settings::load();
+
+    if let Some(file) = flag("--replay") {
+        // 0 plays as fast as possible
+        let speed = match flag("--speed").map_or(Ok(1.), str::parse::<f64>) {
+            Ok(speed) if speed >= 0. => speed,
+            _ => {
+                eprintln!("--speed inválido: {}, esperado um número a partir de 0", flag("--speed").unwrap_or_default());
+                std::process::exit(2)
+            }
+        };
+        settings.replay = Some(ReplaySettings { file: file.to_owned(), speed });
+    }
+    if args.iter().any(|x| x == "--wallboard") && settings.wallboard.is_none() {
+        settings.wallboard = Some(WallboardSettings::default());
+    }
+
+    match args.get(1).map(|x| &x[..]) {
+        #[cfg(not(target_arch = "wasm32"))]
+        Some("console") => ami_monitoring(&settings).unwrap(),
+        #[cfg(not(target_arch = "wasm32"))]
+        Some("stream") => {
+            let format = match flag("--format").map_or(Ok(Format::default()), str::parse) {
+                Ok(format) => format,
+                Err(e) => {
+                    eprintln!("{e}");
+                    std::process::exit(2)
+                }
+            };
+
+            ami_stream(&settings, format).unwrap()
+        },
+        _ => {
+            #[cfg(windows)]
+            if eframe(&settings).is_none() {
+                web(settings).unwrap();
+            }
+            // No native window elsewhere, the bridge serves the web client
+            #[cfg(all(not(windows), not(target_arch = "wasm32")))]
+            web(settings).unwrap();
+        }
     }
 }
 
-/// Call this once from the HTML.
+/// Call this once from the HTML. `options` may set `wsUrl`, otherwise the bridge is
+/// expected on the host that served the page.
 #[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
-pub async fn start(canvas_id: &str) -> Result<(), eframe::wasm_bindgen::JsValue> {
+pub async fn start(canvas_id: &str, options: JsValue) -> Result<(), eframe::wasm_bindgen::JsValue> {
     let mut web_options = eframe::WebOptions::default();
-    eframe::start_web(canvas_id, web_options, Box::new(|cc| Box::new(SipMonitor::new(cc)))).await?;
+    let ws_url = js_sys::Reflect::get(&options, &"wsUrl".into()).ok()
+        .and_then(|x| x.as_string())
+        .unwrap_or_else(default_ws_url);
+
+    eframe::start_web(canvas_id, web_options, Box::new(move |cc| Box::new(SipMonitor::web(cc, ws_url)))).await?;
     Ok(())
 }
 
 #[cfg(windows)]
-fn eframe() -> Option<()> {
+fn eframe(settings: &Settings) -> Option<()> {
     use eframe::Renderer;
 
     let mut native_options = eframe::NativeOptions::default();
     native_options.maximized = true;
+    native_options.fullscreen = settings.wallboard.is_some();
     native_options.renderer = Renderer::Wgpu;
-    eframe::run_native("Sip Monitor", native_options, Box::new(|cc| Box::new(SipMonitor::new(cc)))).ok()
+    let settings = settings.clone();
+    eframe::run_native("Sip Monitor", native_options, Box::new(move |cc| Box::new(SipMonitor::with_settings(cc, settings)))).ok()
 }
 
-#[cfg(windows)]
-fn web() -> IoResult<()> {
-    let mut urls = Urls::new();
-
-    urls.add(|_, _, _| {
-        Path::new("web/index.html").render()
-    });
-
-    for r in ["js", "css"] {
-        urls.append(r, |req, _, _| {
-            Path::new(&format!("web{}", req.url())).render()
-        })
+#[cfg(not(target_arch = "wasm32"))]
+fn web(settings: Settings) -> IoResult<()> {
+    let monitor = match (&settings.replay, &settings.ami) {
+        (Some(replay), _) => Some(Monitor::replay(replay, settings.rules.clone(), settings.bootstrap_window)?),
+        (None, Some(conf)) => Some(Monitor::start(conf, settings.rules.clone(), settings.bootstrap_window)?),
+        (None, None) => None
+    };
+
+    if let Some(monitor) = &monitor {
+        monitor.configure(&settings);
+        spawn_notifier(settings.webhooks.clone(), monitor.subscribe());
     }
 
-    Command::new("cmd.exe")
-        .arg("/C").arg("start").arg("").arg("http://127.0.0.1:61380").spawn()?;
-
-    let client = ClientHTTP::new(urls)?;
-    client.listen_local(61380)?;
-
-    let ws = Server::bind("127.0.0.1:61338")?;
-
-    for stream in ws.filter_map(Result::ok) {
-        let Ok(mut stream) = stream.accept() else { continue };
-        
-        thread::spawn(move || {
-            let msg = stream.recv_message().unwrap();
-
-            if let OwnedMessage::Text(ref s) = msg {
-                let Cred { user, pass, addr } = serde_json::from_str(s).unwrap();
+    let host = if settings.http_bind == "0.0.0.0" { "127.0.0.1" } else { &settings.http_bind };
 
-                ami_web_monitoring(stream, user, pass, addr.parse().unwrap()).unwrap();
-            }
-        });
-    }
-
-    Ok(())
+    #[cfg(windows)]
+    Command::new("cmd.exe")
+        .arg("/C").arg("start").arg("").arg(format!("http://{host}:{}", settings.http_port)).spawn()?;
+    #[cfg(not(windows))]
+    eprintln!("Monitor disponível em http://{host}:{}", settings.http_port);
+
+    // Page, websocket, REST and health share the origin, so one port goes through firewalls and proxies
+    let web_dir = settings.web_dir.clone();
+    let window = settings.bootstrap_window;
+
+    listen((&settings.http_bind[..], settings.http_port), Arc::new(move |req: &Request| match (&req.path[..], &monitor) {
+        ("/ws", _) => websocket(req, move |mut ws| {
+            let Some(msg) = ws.recv()? else { return Ok(()) };
+            let Cred { user, pass, addr } = serde_json::from_str(&msg)?;
+            let ip = addr.parse().map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+
+            ami_web_monitoring(ws, user, pass, ip, window)
+        }),
+        ("/health", monitor) => health(monitor.as_deref()),
+        ("/metrics", Some(monitor)) => Response::new(200, "text/plain; version=0.0.4", metrics(monitor)),
+        ("/events", Some(monitor)) => sse(monitor, req),
+        (path, Some(monitor)) if path.starts_with("/api/") => api(monitor, req),
+        ("/metrics" | "/events", None) => Response::new(503, "text/plain; charset=utf-8", "AMI not configured"),
+        (path, None) if path.starts_with("/api/") => Response::new(503, "text/plain; charset=utf-8", "AMI not configured"),
+        _ => asset(req, web_dir.as_deref())
+    }))
 }
 
-#[cfg(windows)]
-pub fn ami_web_monitoring(mut stream: Client<TcpStream>, user: String, pass: String, ip: Ipv4Addr) -> IoResult<()> {
-    let ami = AmiConnect::new(user, pass, ip, 5038);
+#[cfg(not(target_arch = "wasm32"))]
+pub fn ami_web_monitoring(mut ws: WebSocket, user: String, pass: String, ip: Ipv4Addr, window: usize) -> IoResult<()> {
+    let ami = AmiConnect::new(user, pass, ip, 5038).events([EventClass::Call]);
     let mut ami = Ami::new(ami)?;
+    ami.filter().events(STATUS_EVENTS).send()?;
     let (send, recv) = mpsc::channel();
     let map = Arc::new(Mutex::new(BTreeMap::new()));
     let sync_map = Arc::clone(&map);
 
-    ami.init_treat(move |val| {
-        // println!("{val}\n");
-        match val.as_bytes() {
-            [b'R', b'e', b's', b'p', b'o', b'n', b's', b'e', b':', b' ', b'S', b'u', b'c', b'c', b'e', b's', b's', ..] => {
-                let msg = process(&val);
-
-                send.send(msg).unwrap()
-            },
-            [b'R', b'e', b's', b'p', b'o', b'n', b's', b'e', b':', b' ', ..] => {
-                println!("{val}");
-                send.send(Message::Unknown).unwrap()
-            },
-            [b'E', b'v', b'e', b'n', b't', b':', b' ', b'A', b'o', b'r', b'L', b'i', b's', b't', b'C', b'o', b'm', b'p', b'l', b'e', b't', b'e', ..] => send.send(Message::Complete).unwrap(),
-            [b'E', b'v', b'e', b'n', b't', b':', b' ', b'A', b'o', b'r', b'L', b'i', b's', b't', ..] => {
-                let msg = contact(&val);
-
-                send.send(msg).unwrap()
-            },
-            [b'E', b'v', b'e', b'n', b't', b':', b' ', b'E', b'x', b't', b'e', b'n', b's', b'i', b'o', b'n', b'S', b't', b'a', b't', b'u', b's', ..] => {
-                let Message::Sip(sip, status) = process(&val) else { return };
-                sync_map.lock().unwrap()
-                    .entry(sip)
-                    .and_modify(|x| *x = status);
-
-                send.send(Message::Updated).unwrap()
-            },
-            _ => ()
-        }
-    })?;
+    ami.init_treat(move |val| treat(&val, &send, &sync_map))?;
 
-    ami.pjsip_show_aors().unwrap();
-    let mut contacts = Vec::new();
-
-    if let Message::Start = recv.recv().unwrap() {
-        loop {
-            let msg = recv.recv().unwrap();
-
-            match msg {
-                Message::Complete => break,
-                Message::Contact { contact, name } => {
-                    contacts.push(Contact { contact, name });
-                }
-                _ => ()
-            }
-        }
-    }
-
-    for contact in contacts {
-        ami.extension_state(&contact.name, "ext-local").unwrap();
-
-        let Message::Sip(_, status) = recv.recv().unwrap() else { panic!() };
+    for (contact, status) in bootstrap(&mut ami, &recv, window)? {
         map.lock().unwrap().insert(contact.name, status);
     }
 
     let data = serde_json::to_string(&*map.lock().unwrap())?;
-    stream.send_message(&OwnedMessage::Text(data)).unwrap();
+    ws.send(&data)?;
 
     loop {
-        recv.recv().unwrap();
-        let data = serde_json::to_string(&*map.lock().unwrap())?;
-        stream.send_message(&OwnedMessage::Text(data)).unwrap();
-
-        thread::sleep(Duration::from_millis(1000 / 60));
-    }
-}
-
-pub fn ami_monitoring() -> IoResult<()> {
-    let mut buf = [0u8; 1024];
-
-    print!("IP: ");
-    io::stdout().flush().unwrap();
-    let size = io::stdin().read(&mut buf).unwrap();
-    let ip = String::from_utf8_lossy(&buf[..size]).trim().parse().unwrap();
-
-    print!("user ");
-    io::stdout().flush().unwrap();
-    let size = io::stdin().read(&mut buf).unwrap();
-    let user = String::from_utf8_lossy(&buf[..size]).trim().parse().unwrap();
-
-    print!("Pass: ");
-    io::stdout().flush().unwrap();
-    let size = io::stdin().read(&mut buf).unwrap();
-    let pass = String::from_utf8_lossy(&buf[..size]).trim().to_owned();
-
-    let ami = AmiConnect::new(user, pass, ip, 5038);
-    let mut ami = Ami::new(ami)?;
-    let (send, recv) = mpsc::channel();
-    let map = Arc::new(Mutex::new(BTreeMap::new()));
-    let sync_map = Arc::clone(&map);
-
-    ami.init_treat(move |val| {
-        println!("{val}\n");
-        match val.as_bytes() {
-            [b'R', b'e', b's', b'p', b'o', b'n', b's', b'e', b':', b' ', b'S', b'u', b'c', b'c', b'e', b's', b's', ..] => {
-                let msg = process(&val);
-
-                send.send(msg).unwrap()
-            },
-            [b'R', b'e', b's', b'p', b'o', b'n', b's', b'e', b':', b' ', ..] => {
-                println!("{val}");
-                send.send(Message::Unknown).unwrap()
-            },
-            [b'E', b'v', b'e', b'n', b't', b':', b' ', b'A', b'o', b'r', b'L', b'i', b's', b't', b'C', b'o', b'm', b'p', b'l', b'e', b't', b'e', ..] => send.send(Message::Complete).unwrap(),
-            [b'E', b'v', b'e', b'n', b't', b':', b' ', b'A', b'o', b'r', b'L', b'i', b's', b't', ..] => {
-                let msg = contact(&val);
-
-                send.send(msg).unwrap()
-            },
-            [b'E', b'v', b'e', b'n', b't', b':', b' ', b'E', b'x', b't', b'e', b'n', b's', b'i', b'o', b'n', b'S', b't', b'a', b't', b'u', b's', ..] => {
-                let Message::Sip(sip, status) = process(&val) else { return };
-                sync_map.lock().unwrap()
-                    .entry(Contact::from_name(sip))
-                    .and_modify(|x| *x = status);
-
-                send.send(Message::Updated).unwrap()
-            },
-            _ => ()
-        }
-    })?;
-
-    ami.pjsip_show_aors().unwrap();
-    let mut contacts = Vec::new();
-
-    if let Message::Start = recv.recv().unwrap() {
-        loop {
-            let msg = recv.recv().unwrap();
-
-            match msg {
-                Message::Complete => break,
-                Message::Contact { contact, name } => {
-                    contacts.push(Contact { contact, name });
-                }
-                _ => ()
-            }
+        if recv.recv().is_err() {
+            // The browser reconnects and subscribes again
+            return ws.close()
         }
-    }
-
-    for contact in contacts {
-        ami.extension_state(&contact.name, "ext-local").unwrap();
-
-        let Message::Sip(_, status) = recv.recv().unwrap() else { panic!() };
-        map.lock().unwrap().insert(contact, status);
-    }
-
-    Command::new("cmd").args(["/C", "cls"]).status().unwrap();
-    println!("{}", Some(map.lock().unwrap().iter().map(|(k, v)| format!("{k} = {v}")).collect::<Vec<_>>()).map(|mut x| { x.reverse(); x }).unwrap().join("\r\n"));
 
-    loop {
-        recv.recv().unwrap();
-        Command::new("cmd").args(["/C", "cls"]).status().unwrap();
-        println!("{}", Some(map.lock().unwrap().iter().map(|(k, v)| format!("{k} = {v}")).collect::<Vec<_>>()).map(|mut x| { x.reverse(); x }).unwrap().join("\r\n"));
+        let data = serde_json::to_string(&*map.lock().unwrap())?;
+        ws.send(&data)?;
 
         thread::sleep(Duration::from_millis(1000 / 60));
     }
 }
-
-pub enum Message {
-    Sip(String, SipStatus),
-    Contact {
-        contact: String,
-        name: String
-    },
-    Start,
-    Complete,
-    Updated,
-    Unknown
-}
-
-pub fn process(val: &str) -> Message {
-    if val.contains("EventList: start") {
-        Message::Start
-    } else {
-        sip_status(val)
-    }
-}
-
-fn get_map(val: &str) -> HashMap<&str, &str> {
-    val.lines().map(|x| x.split(": "))
-        .map(|mut x| (x.next().unwrap_or_default(), x.next().unwrap_or_default()))
-        .collect::<HashMap<_, _>>()
-}
-
-fn sip_status(val: &str) -> Message {
-    let v = get_map(val);
-
-    if v.contains_key("Exten") && v.contains_key("Status") && v.contains_key("StatusText") {
-        Message::Sip(v.get("Exten").unwrap().to_string(), SipStatus { status: v.get("Status").unwrap().parse().unwrap_or_default(), status_text: v.get("StatusText").unwrap().to_string() })
-    } else { Message::Unknown }
-}
-
-fn contact(val: &str) -> Message {
-    let v = get_map(val);
-
-    if v.contains_key("ObjectName") && v.contains_key("Contacts") {
-        Message::Contact{ name: v.get("ObjectName").unwrap().parse().unwrap_or_default(), contact: v.get("Contacts").unwrap().to_string() }
-    } else { Message::Unknown }
-}
\ No newline at end of file
@@ -0,0 +1,86 @@
+use crate::*;
+use std::{
+    fmt::Write as _,
+    sync::atomic::Ordering::Relaxed
+};
+
+/// Renders the monitor state in the Prometheus text exposition format.
+pub fn metrics(monitor: &Monitor) -> String {
+    let pbx = label(&monitor.pbx);
+    let mut out = String::new();
+    let mut counts = BTreeMap::<&str, usize>::new();
+    let data = monitor.data.lock().unwrap();
+
+    out += "# HELP sip_extension_state Extension state as reported by ExtensionStatus (-1 unknown, 0 idle, 1 in use, 2 busy, 4 unavailable, 8 ringing, 16 on hold).\n";
+    out += "# TYPE sip_extension_state gauge\n";
+    for (exten, status) in data.iter() {
+        let _ = writeln!(out, "sip_extension_state{{exten=\"{}\",pbx=\"{pbx}\"}} {}", label(exten), status.status);
+        *counts.entry(&status.status_text).or_default() += 1;
+    }
+
+    out += "# HELP sip_extensions Number of extensions in each state.\n";
+    out += "# TYPE sip_extensions gauge\n";
+    for (status, count) in counts {
+        let _ = writeln!(out, "sip_extensions{{pbx=\"{pbx}\",status=\"{}\"}} {count}", label(status));
+    }
+    drop(data);
+
+    let calls = active_calls(&monitor.channels.lock().unwrap());
+    let stats = &monitor.stats;
+    let gauges = [
+        ("sip_active_calls", "gauge", "Calls in progress, counting the channels of a call once.", calls as f64),
+        ("sip_monitor_ami_up", "gauge", "Whether the AMI session is connected.", stats.connected.load(Relaxed) as u8 as f64),
+        ("sip_monitor_ami_reconnects_total", "counter", "AMI sessions established after a disconnection.", stats.reconnects.load(Relaxed) as f64),
+        ("sip_monitor_ami_frames_total", "counter", "Frames read from the AMI socket.", stats.frames.load(Relaxed) as f64),
+        ("sip_monitor_bootstrap_seconds", "gauge", "Duration of the last bootstrap.", stats.bootstrap_ms.load(Relaxed) as f64 / 1000.)
+    ];
+
+    for (name, kind, help, value) in gauges {
+        let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} {kind}\n{name}{{pbx=\"{pbx}\"}} {value}");
+    }
+
+    out
+}
+
+/// Escapes a label value.
+fn label(val: &str) -> String {
+    val.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_metric_has_its_help_and_type() {
+        let text = metrics(&capture::replayed());
+        let mut described = HashSet::new();
+
+        for line in text.lines() {
+            match line.split(' ').collect::<Vec<_>>()[..] {
+                ["#", "HELP", name, ..] => assert!(described.insert(name), "{name} described twice"),
+                ["#", "TYPE", name, kind] => assert!(described.contains(name) && ["gauge", "counter"].contains(&kind), "{line}"),
+                _ => {
+                    let name = &line[..line.find('{').unwrap()];
+                    assert!(described.contains(name), "{name} without HELP");
+                }
+            }
+        }
+
+        assert!(text.contains("sip_extension_state{exten=\"2002\",pbx=\"10.0.0.1\"} 1\n"));
+        assert!(text.contains("sip_extensions{pbx=\"10.0.0.1\",status=\"InUse\"} 1\n"));
+        assert!(text.contains("sip_active_calls{pbx=\"10.0.0.1\"} 1\n"));
+        assert!(text.contains("sip_monitor_ami_up{pbx=\"10.0.0.1\"} 0\n"));
+    }
+
+    #[test]
+    fn label_values_are_escaped() {
+        assert_eq!(label("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+
+        let monitor = capture::replayed();
+        let status = monitor.data.lock().unwrap()["2001"].clone();
+        monitor.data.lock().unwrap().insert("20\"01".to_owned(), status);
+
+        assert!(metrics(&monitor).contains("sip_extension_state{exten=\"20\\\"01\",pbx=\"10.0.0.1\"} 0\n"));
+    }
+}
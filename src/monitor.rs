@@ -0,0 +1,554 @@
+use crate::*;
+use std::{
+    time::Instant,
+    sync::atomic::{
+        AtomicBool,
+        AtomicU64,
+        Ordering::Relaxed
+    }
+};
+
+/// How long `call` waits for each frame of a reply.
+const CALL_TIMEOUT: Duration = Duration::from_secs(10);
+/// The AstDB sends no events, DND and forwarding set from the phones show up on the next read.
+const FEATURES_POLL: Duration = Duration::from_secs(30);
+/// Without `DBGetTree` it takes a `DBGet` per extension and feature, so those reads go further apart.
+const FEATURES_FALLBACK_POLL: Duration = Duration::from_secs(300);
+/// `Message` of the AstDB actions for a missing key or an empty family.
+const DB_NOT_FOUND: &str = "Database entry not found";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MonitorEvent {
+    Status(StatusChange),
+    Alert(Alert)
+}
+
+#[derive(Debug, Default)]
+pub struct Stats {
+    pub connected: AtomicBool,
+    pub reconnects: AtomicU64,
+    /// Frames read from the AMI socket
+    pub frames: AtomicU64,
+    /// Duration of the last bootstrap, in milliseconds
+    pub bootstrap_ms: AtomicU64
+}
+
+/// A logged in session with its bootstrapped state, kept up to date by the AMI thread
+/// and logged in again whenever the connection drops.
+///
+/// Whoever holds more than one of the maps at once takes them in this order: `history`, `data`,
+/// `regs`, `trunks`, `queues`, `channels`, `directory`, `features`. The GUI copies them one at a time instead.
+pub struct Monitor {
+    pub pbx: String,
+    /// `None` while reconnecting
+    pub ami: Mutex<Option<Ami>>,
+    pub data: AllData,
+    pub regs: Registrations,
+    pub queues: Queues,
+    pub channels: Channels,
+    pub directory: Directory,
+    pub trunks: Trunks,
+    pub features: Features,
+    pub parking: Parking,
+    pub conferences: Conferences,
+    pub alerts: Alerts,
+    pub history: Mutex<History>,
+    /// Raw frames of the session, for the AMI inspector
+    pub frames: Frames,
+    pub stats: Arc<Stats>,
+    source: Source,
+    /// `ExtensionState` requests in flight during the bootstrap
+    window: usize,
+    /// Whether the PBX has `DBGetTree`, until it says otherwise
+    db_get_tree: AtomicBool,
+    /// Last read of the features one extension at a time, on PBXs without `DBGetTree`
+    features_read: Mutex<Option<Instant>>,
+    /// Frames of the replies `call` is waiting for, by ActionID
+    replies: Arc<Mutex<HashMap<u64, Sender<String>>>>,
+    subscribers: Arc<Mutex<Vec<Sender<MonitorEvent>>>>
+}
+
+enum Source {
+    Live(AmiSettings, Option<Arc<Recorder>>),
+    Replay(Arc<Mutex<Player>>)
+}
+
+impl Monitor {
+    /// Logs in, loads the extension states, registrations and channels and starts the rules engine.
+    pub fn start(conf: &AmiSettings, rules: Vec<Rule>, window: usize) -> Result<Arc<Self>> {
+        let recorder = conf.record.as_ref().map(Recorder::create).transpose()?;
+
+        Self::with_source(conf.addr.clone(), Source::Live(conf.clone(), recorder), rules, window)
+    }
+
+    /// Same as `start`, fed by a capture. Every consumer sees it as a live PBX until the capture ends.
+    pub fn replay(conf: &ReplaySettings, rules: Vec<Rule>, window: usize) -> Result<Arc<Self>> {
+        let player = Player::open(&conf.file, conf.speed)?;
+        let pbx = player.lock().unwrap().pbx.clone();
+
+        Self::with_source(pbx, Source::Replay(player), rules, window)
+    }
+
+    fn with_source(pbx: String, source: Source, rules: Vec<Rule>, window: usize) -> Result<Arc<Self>> {
+        let mut engine = RuleEngine::new(rules);
+        let fired = engine.subscribe();
+
+        let monitor = Arc::new(Self {
+            pbx,
+            ami: Mutex::new(None),
+            data: Default::default(),
+            regs: Default::default(),
+            queues: Default::default(),
+            channels: Default::default(),
+            directory: Default::default(),
+            trunks: Default::default(),
+            features: Default::default(),
+            parking: Default::default(),
+            conferences: Default::default(),
+            alerts: engine.active(),
+            history: Default::default(),
+            frames: Default::default(),
+            stats: Default::default(),
+            source,
+            window,
+            db_get_tree: AtomicBool::new(true),
+            features_read: Default::default(),
+            replies: Default::default(),
+            subscribers: Default::default()
+        });
+
+        let recv = monitor.session()?;
+        engine.spawn(Arc::clone(&monitor.data), Arc::clone(&monitor.regs), Arc::clone(&monitor.queues));
+
+        let sync_monitor = Arc::clone(&monitor);
+        thread::spawn(move || sync_monitor.run(recv));
+
+        // A replay has the reads of the capture, and only those
+        if let Source::Live(..) = monitor.source {
+            let sync_monitor = Arc::clone(&monitor);
+            thread::spawn(move || loop {
+                thread::sleep(FEATURES_POLL);
+
+                if sync_monitor.stats.connected.load(Relaxed) {
+                    if let Err(e) = sync_monitor.refresh_features() {
+                        elog!("Falha ao ler DND e desvios de {}: {e}", sync_monitor.pbx);
+                    }
+                }
+            });
+        }
+
+        let subscribers = Arc::clone(&monitor.subscribers);
+        thread::spawn(move || {
+            for alert in fired {
+                broadcast(&subscribers, MonitorEvent::Alert(alert));
+            }
+        });
+
+        Ok(monitor)
+    }
+
+    /// Receives every status change and alert transition from now on.
+    pub fn subscribe(&self) -> Receiver<MonitorEvent> {
+        let (send, recv) = mpsc::channel();
+        self.subscribers.lock().unwrap().push(send);
+
+        recv
+    }
+
+    /// Applies what the settings add to the PBX state: the trunks and the directory, if any.
+    /// A bad directory file is reported and the PBX names are kept.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn configure(&self, settings: &Settings) {
+        configure_trunks(&self.trunks, &self.regs, &settings.trunks);
+
+        let Some(path) = &settings.directory else { return };
+
+        match load_directory(path) {
+            Ok(entries) => merge_directory(&self.directory, entries),
+            Err(e) => elog!("Diretório inválido em {path}: {e}")
+        }
+    }
+
+    /// Sends an arbitrary action on the current session, returning its ActionID.
+    /// The reply shows up in `frames` like any other frame.
+    pub fn action(&self, action: &str, headers: &[(&str, &str)]) -> Result<u64> {
+        let mut ami = self.ami.lock().unwrap();
+        let ami = ami.as_mut().ok_or_else(|| io::Error::from(io::ErrorKind::NotConnected))?;
+        let (id, frame) = ami.action_frame(action, headers)?;
+        self.frames.lock().unwrap().push(Direction::Out, frame.trim());
+
+        Ok(id)
+    }
+
+    /// Sends an action and waits for its whole reply: the response, plus the events of the list when there's one.
+    pub fn call(&self, action: &str, headers: &[(&str, &str)]) -> Result<Vec<String>> {
+        let (send, recv) = mpsc::channel();
+        let id = {
+            // Held until the sender is in, so the reply can't get there first
+            let mut replies = self.replies.lock().unwrap();
+            let id = self.action(action, headers)?;
+            replies.insert(id, send);
+
+            id
+        };
+
+        let mut frames = Vec::new();
+        let result = loop {
+            let frame = match recv.recv_timeout(CALL_TIMEOUT) {
+                Ok(frame) => frame,
+                Err(_) => break Err(io::Error::new(io::ErrorKind::TimedOut, format!("{action} sem resposta")))
+            };
+
+            if frames.is_empty() && !frame.starts_with("Response: Success") {
+                break Err(io::Error::new(io::ErrorKind::Other, get_map(&frame).get("Message").unwrap_or(&"Error").to_string()))
+            }
+
+            let done = frame.contains("EventList: Complete") || (frames.is_empty() && !frame.contains("EventList: start"));
+            frames.push(frame);
+
+            if done {
+                break Ok(frames)
+            }
+        };
+
+        self.replies.lock().unwrap().remove(&id);
+
+        result
+    }
+
+    /// Detail of a PJSIP endpoint, see `EndpointDetail`.
+    pub fn endpoint_detail(&self, endpoint: &str) -> Result<EndpointDetail> {
+        self.call("PJSIPShowEndpoint", &[("Endpoint", endpoint)]).map(|frames| EndpointDetail::parse(&frames))
+    }
+
+    /// Blind transfers the call of `exten`: the other party goes to `target`, `exten` hangs up.
+    pub fn transfer(&self, exten: &str, target: &str, context: &str) -> Result<()> {
+        let channel = self.channels.lock().unwrap().values()
+            .find(|x| x.endpoint() == exten && x.state == "Up")
+            .map(|x| x.channel.clone())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("{exten} sem chamada ativa")))?;
+
+        self.call("BlindTransfer", &[("Channel", &channel), ("Exten", target), ("Context", context)]).map(|_| ())
+    }
+
+    /// Rings `from` and, once answered, calls `to`.
+    pub fn dial(&self, from: &str, to: &str, context: &str) -> Result<()> {
+        self.call("Originate", &[
+            ("Channel", &format!("PJSIP/{from}")),
+            ("Exten", to),
+            ("Context", context),
+            ("Priority", "1"),
+            ("CallerID", &format!("{to} <{to}>")),
+            ("Async", "true")
+        ]).map(|_| ())
+    }
+
+    /// Rings `from` and, once answered, takes the call ringing at `exten`.
+    pub fn pickup(&self, from: &str, exten: &str) -> Result<()> {
+        self.call("Originate", &[
+            ("Channel", &format!("PJSIP/{from}")),
+            ("Application", "PickupChan"),
+            ("Data", &format!("PJSIP/{exten}")),
+            ("CallerID", &format!("{exten} <{exten}>")),
+            ("Async", "true")
+        ]).map(|_| ())
+    }
+
+    /// Value of `feature` for `exten` in the AstDB, `None` when not set.
+    pub fn feature(&self, exten: &str, feature: Feature) -> Result<Option<String>> {
+        match self.call("DBGet", &[("Family", feature.family()), ("Key", exten)]) {
+            Ok(frames) => Ok(frames.iter().find_map(|x| db_entry(x)).map(|(_, _, val)| val)),
+            Err(e) if e.to_string() == DB_NOT_FOUND => Ok(None),
+            Err(e) => Err(e)
+        }
+    }
+
+    /// Turns `feature` on with `val`, or off with `None`, then reads it back into `features`.
+    pub fn set_feature(&self, exten: &str, feature: Feature, val: Option<&str>) -> Result<()> {
+        let family = feature.family();
+        let result = match val {
+            Some(val) => self.call("DBPut", &[("Family", family), ("Key", exten), ("Val", val)]),
+            None => self.call("DBDel", &[("Family", family), ("Key", exten)])
+        };
+
+        // The BLF keys FreePBX gives DND and forwarding watch these, not the AstDB
+        let blf = match result {
+            Ok(_) if matches!(feature, Feature::Dnd | Feature::Cf) => {
+                let device = format!("DEVICE_STATE(Custom:{family}{exten})");
+                self.call("Setvar", &[("Variable", &device), ("Value", if val.is_some() { "BUSY" } else { "NOT_INUSE" })]).map(|_| ())
+            },
+            _ => Ok(())
+        };
+
+        // Read back even when the lamp failed, the AstDB may have changed anyway
+        let current = self.feature(exten, feature);
+        if let Ok(current) = &current {
+            self.features.lock().unwrap().entry(exten.to_owned()).or_default().set(feature, current.as_deref());
+        }
+
+        result.and(blf).and(current.map(|_| ()))
+    }
+
+    /// Reads the DND and forwarding families again, dropping the keys that are gone.
+    /// Falls back to a `DBGet` per extension on PBXs without `DBGetTree`.
+    pub fn refresh_features(&self) -> Result<()> {
+        if !self.db_get_tree.load(Relaxed) {
+            return self.refresh_features_by_exten()
+        }
+
+        for feature in Feature::ALL {
+            let frames = match self.call("DBGetTree", &[("Family", feature.family())]) {
+                Ok(frames) => frames,
+                // `Invalid/unknown command: DBGetTree. Use Action: ListCommands to show available commands.`
+                Err(e) if e.to_string().starts_with("Invalid/unknown command") => {
+                    self.db_get_tree.store(false, Relaxed);
+                    return self.refresh_features_by_exten()
+                },
+                Err(e) if e.to_string() == DB_NOT_FOUND => Vec::new(),
+                Err(e) => return Err(e)
+            };
+
+            // What's there was set by `treat_features` as the frames went by
+            let present = frames.iter().filter_map(|x| db_entry(x)).map(|(_, exten, _)| exten).collect::<HashSet<_>>();
+
+            for (exten, entry) in self.features.lock().unwrap().iter_mut() {
+                if !present.contains(exten) {
+                    entry.set(feature, None);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `refresh_features` for PBXs without `DBGetTree`, at most once per `FEATURES_FALLBACK_POLL`.
+    fn refresh_features_by_exten(&self) -> Result<()> {
+        {
+            let mut read = self.features_read.lock().unwrap();
+
+            if read.map_or(false, |x| x.elapsed() < FEATURES_FALLBACK_POLL) {
+                return Ok(())
+            }
+            *read = Some(Instant::now());
+        }
+
+        let extens = self.data.lock().unwrap().keys().cloned().collect::<Vec<_>>();
+
+        for exten in extens {
+            for feature in Feature::ALL {
+                let current = self.feature(&exten, feature)?;
+                self.features.lock().unwrap().entry(exten.clone()).or_default().set(feature, current.as_deref());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Lists the participants of every active room, `treat_conferences` takes them as they arrive.
+    fn load_conferences(&self) -> Result<()> {
+        let rooms = match self.call("ConfbridgeListRooms", &[]) {
+            Ok(frames) => frames,
+            // No active conferences
+            Err(e) if e.kind() == io::ErrorKind::Other => return Ok(()),
+            Err(e) => return Err(e)
+        };
+
+        for room in rooms.iter().filter_map(|x| get_map(x).get("Conference").map(|x| x.to_string())) {
+            self.call("ConfbridgeList", &[("Conference", &room)])?;
+        }
+
+        Ok(())
+    }
+
+    /// Asks the presence of every extension whose hint has a provider, publishing what changed.
+    /// The replies carry no provider, so one at a time; stops at the first failure or once disconnected.
+    fn load_presence(&self) {
+        let providers = self.data.lock().unwrap().values()
+            .filter_map(|x| x.presence.as_ref().map(|x| x.provider.clone()))
+            .collect::<HashSet<_>>();
+
+        for provider in providers {
+            if !self.stats.connected.load(Relaxed) {
+                return
+            }
+
+            let reply = match self.call("PresenceState", &[("Provider", &provider)]) {
+                Ok(frames) => frames.concat(),
+                // Unknown provider
+                Err(e) if e.kind() == io::ErrorKind::Other => continue,
+                Err(e) => {
+                    elog!("Falha ao ler a presença dos ramais de {}: {e}", self.pbx);
+                    return
+                }
+            };
+            let mut changes = Vec::new();
+
+            for (exten, status) in self.data.lock().unwrap().iter_mut() {
+                if status.presence.as_ref().map_or(true, |x| x.provider != provider) {
+                    continue
+                }
+                let old = status.clone();
+
+                status.presence.as_mut().unwrap().update(&reply);
+                if old.presence != status.presence {
+                    changes.push(StatusChange { id: 0, pbx: String::new(), exten: exten.clone(), old, new: status.clone(), at: unix_now() });
+                }
+            }
+
+            // `history` comes before `data`
+            for change in changes {
+                self.publish(change);
+            }
+        }
+    }
+
+    /// Asks the caller ID names of `extens` one at a time, `treat_directory` takes them as they arrive.
+    /// Stops at the first failure, the next session asks again.
+    fn load_endpoints(&self, extens: Vec<String>) {
+        for exten in extens {
+            if !self.stats.connected.load(Relaxed) {
+                return
+            }
+            if let Err(e) = self.call("PJSIPShowEndpoint", &[("Endpoint", &exten)]) {
+                elog!("Falha ao ler os nomes dos ramais de {}: {e}", self.pbx);
+                return
+            }
+        }
+    }
+
+    /// Logs in and bootstraps. Each session feeds its own channel, so a stale one can't be mistaken for the current.
+    fn session(self: &Arc<Self>) -> Result<Receiver<Message>> {
+        let started = Instant::now();
+        let mut ami = match &self.source {
+            Source::Live(conf, recorder) => {
+                let addr = conf.addr.parse().map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+                let mut ami = AmiConnect::new(conf.user.clone(), conf.pass.clone(), addr, conf.port)
+                    .events([EventClass::Call, EventClass::System, EventClass::Agent]);
+
+                if let Some(recorder) = recorder {
+                    ami = ami.record(Arc::clone(recorder));
+                }
+
+                Ami::new(ami)?
+            },
+            Source::Replay(player) => Ami::replay(player)?
+        };
+        ami.filter().events(STATUS_EVENTS).events(CONTACT_EVENTS).events(QUEUE_EVENTS).events(CHANNEL_EVENTS).events(TRUNK_EVENTS)
+            .events(PARKING_EVENTS).events(CONFERENCE_EVENTS).events(PRESENCE_EVENTS).send()?;
+        let (send, recv) = mpsc::channel();
+        let (sync_map, sync_regs, sync_queues, sync_channels) = (Arc::clone(&self.data), Arc::clone(&self.regs), Arc::clone(&self.queues), Arc::clone(&self.channels));
+        let sync_stats = Arc::clone(&self.stats);
+        let sync_frames = Arc::clone(&self.frames);
+        let sync_directory = Arc::clone(&self.directory);
+        let sync_trunks = Arc::clone(&self.trunks);
+        let sync_features = Arc::clone(&self.features);
+        let (sync_parking, sync_conferences) = (Arc::clone(&self.parking), Arc::clone(&self.conferences));
+        let sync_replies = Arc::clone(&self.replies);
+
+        ami.init_treat(move |val| {
+            sync_stats.frames.fetch_add(1, Relaxed);
+            sync_frames.lock().unwrap().push(Direction::In, &val);
+
+            if let Some(reply) = sync_replies.lock().unwrap().get(&action_id(&val)) {
+                let _ = reply.send(val.clone());
+            }
+            treat(&val, &send, &sync_map);
+            treat_presence(&val, &send, &sync_map);
+            treat_registrations(&val, &sync_regs);
+            treat_trunks(&val, &sync_trunks, &sync_regs);
+            treat_queues(&val, &sync_queues);
+            treat_channels(&val, &sync_channels);
+            treat_directory(&val, &sync_directory);
+            treat_features(&val, &sync_features);
+            treat_parking(&val, &sync_parking);
+            treat_conferences(&val, &sync_conferences);
+        })?;
+
+        let states = bootstrap(&mut ami, &recv, self.window)?;
+        let extens = states.iter().map(|(contact, _)| contact.name.clone()).collect::<Vec<_>>();
+        merge_directory(&self.directory, states.iter().map(|(contact, _)| contact.clone()).collect());
+        *self.data.lock().unwrap() = states.into_iter().map(|(contact, status)| (contact.name, status)).collect();
+        self.regs.lock().unwrap().clear();
+        self.channels.lock().unwrap().clear();
+        ami.pjsip_show_contacts()?;
+        ami.pjsip_show_registrations_outbound()?;
+        ami.core_show_channels()?;
+
+        self.features.lock().unwrap().clear();
+        // Read again on the next poll when the families can't be listed
+        *self.features_read.lock().unwrap() = None;
+        for feature in Feature::ALL {
+            ami.db_get_tree(feature.family())?;
+        }
+
+        self.parking.lock().unwrap().clear();
+        self.conferences.lock().unwrap().clear();
+        ami.parkinglots()?;
+        ami.parked_calls()?;
+
+        self.stats.bootstrap_ms.store(started.elapsed().as_millis() as u64, Relaxed);
+        self.stats.connected.store(true, Relaxed);
+        *self.ami.lock().unwrap() = Some(ami);
+
+        if let Err(e) = self.load_conferences() {
+            elog!("Falha ao listar as conferências de {}: {e}", self.pbx);
+        }
+        // Presence and caller ID names, a reply per provider or extension is too much for the
+        // bootstrap. A replay sends them too, so the player can pair the recorded replies
+        let sync_monitor = Arc::clone(self);
+        thread::spawn(move || {
+            sync_monitor.load_presence();
+            sync_monitor.load_endpoints(extens);
+        });
+
+        Ok(recv)
+    }
+
+    /// Numbers the change in `history` and sends it to the subscribers.
+    fn publish(&self, mut change: StatusChange) {
+        change.pbx = self.pbx.clone();
+        let change = self.history.lock().unwrap().push(change);
+
+        broadcast(&self.subscribers, MonitorEvent::Status(change));
+    }
+
+    /// Broadcasts status changes until the session drops, then logs in again with backoff.
+    ///
+    /// A replay plays its next recorded session instead, and closes the subscriptions once there's none left.
+    fn run(self: Arc<Self>, mut recv: Receiver<Message>) {
+        loop {
+            for msg in &recv {
+                let Message::Updated(change) = msg else { continue };
+
+                self.publish(change);
+            }
+
+            elog!("Conexão com {} perdida", self.pbx);
+            self.stats.connected.store(false, Relaxed);
+            *self.ami.lock().unwrap() = None;
+            let mut delay = 1;
+
+            recv = loop {
+                match self.session() {
+                    Ok(recv) => break recv,
+                    Err(e) if matches!(self.source, Source::Replay(_)) => {
+                        elog!("Reprodução de {} encerrada: {e}", self.pbx);
+                        self.subscribers.lock().unwrap().clear();
+                        return
+                    },
+                    Err(e) => {
+                        elog!("Reconectando a {} em {delay}s: {e}", self.pbx);
+                        thread::sleep(Duration::from_secs(delay));
+                        delay = (delay * 2).min(60);
+                    }
+                }
+            };
+            self.stats.reconnects.fetch_add(1, Relaxed);
+        }
+    }
+}
+
+fn broadcast(subscribers: &Mutex<Vec<Sender<MonitorEvent>>>, event: MonitorEvent) {
+    subscribers.lock().unwrap().retain(|x| x.send(event.clone()).is_ok());
+}
@@ -0,0 +1,297 @@
+use crate::*;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(not(target_arch = "wasm32"))]
+use hmac::{
+    Hmac,
+    Mac
+};
+#[cfg(not(target_arch = "wasm32"))]
+use sha2::Sha256;
+use serde_json::Value;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Webhook {
+    pub url: String,
+    /// Event types to send (`status`, `alert`), all of them when empty
+    #[serde(default)]
+    pub events: Vec<String>,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Body with `{{field}}` placeholders (`{{exten}}`, `{{new.status_text}}`, ...), the event JSON when absent
+    pub template: Option<String>,
+    /// Signs the body with HMAC-SHA256 in the `X-Signature-256` header
+    pub secret: Option<String>,
+    #[serde(default = "default_retries")]
+    pub retries: u32,
+    #[serde(default = "default_rate")]
+    pub per_minute: u32
+}
+
+fn default_retries() -> u32 {
+    5
+}
+
+fn default_rate() -> u32 {
+    30
+}
+
+impl Webhook {
+    pub fn wants(&self, event: &MonitorEvent) -> bool {
+        let kind = match event {
+            MonitorEvent::Status(_) => "status",
+            MonitorEvent::Alert(_) => "alert"
+        };
+
+        self.events.is_empty() || self.events.iter().any(|x| x == kind)
+    }
+
+    /// Renders the request body for `event`. Placeholders are replaced by the JSON value
+    /// at the dotted path, strings being JSON escaped without their quotes.
+    pub fn body(&self, event: &MonitorEvent) -> String {
+        let value = serde_json::to_value(event).unwrap_or_default();
+        let Some(template) = &self.template else { return value.to_string() };
+        let mut body = String::with_capacity(template.len());
+        let mut rest = &template[..];
+
+        while let Some(start) = rest.find("{{") {
+            let Some(end) = rest[start..].find("}}") else { break };
+            let path = rest[start + 2..start + end].trim();
+            let field = path.split('.').fold(&value, |x, key| x.get(key).unwrap_or(&Value::Null));
+
+            body += &rest[..start];
+            match field {
+                Value::String(x) => {
+                    let quoted = Value::String(x.clone()).to_string();
+                    body += &quoted[1..quoted.len() - 1];
+                },
+                Value::Null => (),
+                x => body += &x.to_string()
+            }
+            rest = &rest[start + end + 2..];
+        }
+
+        body + rest
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+struct Delivery {
+    body: String,
+    attempts: u32,
+    next: Instant
+}
+
+/// POSTs monitor events to the configured webhooks, each one on its own thread.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn spawn_notifier(hooks: Vec<Webhook>, events: Receiver<MonitorEvent>) {
+    let hooks = hooks.into_iter().map(|hook| {
+        let (send, recv) = mpsc::channel();
+        let sync_hook = hook.clone();
+        thread::spawn(move || deliver(sync_hook, recv));
+
+        (hook, send)
+    }).collect::<Vec<_>>();
+
+    thread::spawn(move || {
+        for event in events {
+            for (hook, send) in hooks.iter().filter(|(hook, _)| hook.wants(&event)) {
+                let _ = send.send(hook.body(&event));
+            }
+        }
+    });
+}
+
+/// Sends the queued bodies in order, limited to `per_minute` requests and retrying
+/// failures with exponential backoff up to `retries` times. A 4xx other than 429 won't
+/// get better by trying again, so it's dropped right away.
+#[cfg(not(target_arch = "wasm32"))]
+fn deliver(hook: Webhook, bodies: Receiver<String>) {
+    let rate = Duration::from_secs(60) / hook.per_minute.max(1);
+    let mut queue = VecDeque::<Delivery>::new();
+    let mut allowed = Instant::now();
+
+    loop {
+        let timeout = queue.front().map_or(Duration::from_secs(60), |x| x.next.max(allowed).saturating_duration_since(Instant::now()));
+
+        match bodies.recv_timeout(timeout) {
+            Ok(body) => queue.push_back(Delivery { body, attempts: 0, next: Instant::now() }),
+            Err(mpsc::RecvTimeoutError::Timeout) => (),
+            Err(mpsc::RecvTimeoutError::Disconnected) if queue.is_empty() => break,
+            Err(mpsc::RecvTimeoutError::Disconnected) => thread::sleep(timeout)
+        }
+
+        let now = Instant::now();
+        if queue.front().map_or(true, |x| x.next > now) || now < allowed {
+            continue
+        }
+
+        let mut delivery = queue.pop_front().unwrap();
+        allowed = now + rate;
+
+        if let Err(e) = post(&hook, &delivery.body) {
+            delivery.attempts += 1;
+
+            if delivery.attempts > hook.retries || !retryable(&e) {
+                elog!("Webhook {} descartado após {} tentativas: {e}", hook.url, delivery.attempts);
+            } else {
+                delivery.next = now + Duration::from_secs(1 << delivery.attempts.min(8));
+                queue.push_front(delivery);
+            }
+        }
+    }
+}
+
+/// Network failures, server errors and `429 Too Many Requests`.
+#[cfg(not(target_arch = "wasm32"))]
+fn retryable(e: &ureq::Error) -> bool {
+    match e {
+        ureq::Error::Status(code, _) => *code >= 500 || *code == 429,
+        ureq::Error::Transport(_) => true
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn post(hook: &Webhook, body: &str) -> std::result::Result<(), Box<ureq::Error>> {
+    let mut req = ureq::post(&hook.url)
+        .timeout(Duration::from_secs(10))
+        .set("Content-Type", "application/json");
+
+    for (key, value) in &hook.headers {
+        req = req.set(key, value);
+    }
+
+    if let Some(secret) = &hook.secret {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+        mac.update(body.as_bytes());
+        let hex = mac.finalize().into_bytes().iter().map(|x| format!("{x:02x}")).collect::<String>();
+
+        req = req.set("X-Signature-256", &format!("sha256={hex}"));
+    }
+
+    req.send_string(body).map(|_| ()).map_err(Box::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::BufReader,
+        net::TcpListener
+    };
+
+    fn hook(url: String) -> Webhook {
+        Webhook { url, events: Vec::new(), headers: BTreeMap::new(), template: None, secret: None, retries: 5, per_minute: 6000 }
+    }
+
+    fn change(exten: &str) -> MonitorEvent {
+        let status = |status, text: &str| SipStatus { status, status_text: text.to_owned(), presence: None };
+
+        MonitorEvent::Status(StatusChange { id: 7, pbx: "10.0.0.1".to_owned(), exten: exten.to_owned(), old: status(0, "Idle"), new: status(1, "InUse"), at: 0 })
+    }
+
+    /// Answers one request with `status`, returning its head and body.
+    fn serve(listener: &TcpListener, status: &str) -> (String, String) {
+        let (mut tcp, _) = listener.accept().unwrap();
+        let mut read = BufReader::new(tcp.try_clone().unwrap());
+        let mut head = String::new();
+
+        while read.read_line(&mut head).unwrap() > 2 {}
+
+        let length = head.lines()
+            .find_map(|x| x.to_lowercase().strip_prefix("content-length: ").map(|x| x.trim().parse::<usize>().unwrap()))
+            .unwrap_or_default();
+        let mut body = vec![0; length];
+        read.read_exact(&mut body).unwrap();
+        tcp.write_all(format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n").as_bytes()).unwrap();
+
+        (head, String::from_utf8(body).unwrap())
+    }
+
+    #[test]
+    fn template_takes_the_fields_of_the_event() {
+        let hook = Webhook { template: Some(r#"{"text": "{{exten}} {{new.status_text}} {{pbx}} {{id}}{{missing.field}}"}"#.to_owned()), ..hook(String::new()) };
+
+        assert_eq!(hook.body(&change("2001")), r#"{"text": "2001 InUse 10.0.0.1 7"}"#);
+        assert_eq!(hook.body(&change("a\"b")), r#"{"text": "a\"b InUse 10.0.0.1 7"}"#);
+    }
+
+    #[test]
+    fn without_template_the_body_is_the_event() {
+        let body = hook(String::new()).body(&change("2001"));
+        let value: Value = serde_json::from_str(&body).unwrap();
+
+        assert_eq!(value["type"], "status");
+        assert_eq!(value["exten"], "2001");
+    }
+
+    #[test]
+    fn wants_filters_by_event_type() {
+        let hook = Webhook { events: vec!["alert".to_owned()], ..hook(String::new()) };
+
+        assert!(!hook.wants(&change("2001")));
+    }
+
+    /// Signed like the well known HMAC-SHA256 of `key` and the quick brown fox, retried after a 500.
+    #[test]
+    fn signs_and_retries_on_server_errors() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let hook = Webhook {
+            template: Some("The quick brown fox jumps over the lazy dog".to_owned()),
+            secret: Some("key".to_owned()),
+            headers: [("X-Token".to_owned(), "abc".to_owned())].into(),
+            ..hook(url)
+        };
+        let (send, recv) = mpsc::channel();
+        spawn_notifier(vec![hook], recv);
+        send.send(change("2001")).unwrap();
+
+        let (head, body) = serve(&listener, "500 Internal Server Error");
+        let failed = Instant::now();
+        let (retry, again) = serve(&listener, "200 OK");
+
+        assert!(head.starts_with("POST /hook "));
+        assert!(head.contains("X-Token: abc"));
+        assert!(head.contains("X-Signature-256: sha256=f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"));
+        assert_eq!(body, "The quick brown fox jumps over the lazy dog");
+        assert_eq!((retry.lines().next(), again), (head.lines().next(), body));
+        // First backoff is two seconds
+        assert!(failed.elapsed() >= Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn drops_client_errors_right_away() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let hook = Webhook { template: Some("{{exten}}".to_owned()), ..hook(format!("http://{}/", listener.local_addr().unwrap())) };
+        let (send, recv) = mpsc::channel();
+        spawn_notifier(vec![hook], recv);
+        send.send(change("2001")).unwrap();
+        send.send(change("2002")).unwrap();
+
+        let (_, first) = serve(&listener, "401 Unauthorized");
+        let failed = Instant::now();
+        let (_, second) = serve(&listener, "200 OK");
+
+        // The next one goes out without waiting for a backoff
+        assert_eq!((&first[..], &second[..]), ("2001", "2002"));
+        assert!(failed.elapsed() < Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn keeps_to_the_rate() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let hook = Webhook { per_minute: 60, template: Some("{{exten}}".to_owned()), ..hook(format!("http://{}/", listener.local_addr().unwrap())) };
+        let (send, recv) = mpsc::channel();
+        spawn_notifier(vec![hook], recv);
+        send.send(change("2001")).unwrap();
+        send.send(change("2002")).unwrap();
+
+        let (_, first) = serve(&listener, "204 No Content");
+        let sent = Instant::now();
+        let (_, second) = serve(&listener, "204 No Content");
+
+        assert_eq!((&first[..], &second[..]), ("2001", "2002"));
+        assert!(sent.elapsed() >= Duration::from_millis(900));
+    }
+}
@@ -0,0 +1,135 @@
+use crate::*;
+
+/// Events the parking panel consumes (class `call`).
+pub const PARKING_EVENTS: &[&str] = &["ParkedCall", "UnParkedCall", "ParkedCallTimeOut", "ParkedCallGiveUp", "ParkedCallSwap"];
+
+/// Parking lots keyed by name.
+pub type Parking = Arc<Mutex<BTreeMap<String, ParkingLot>>>;
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct ParkingLot {
+    pub name: String,
+    /// First and last space, unknown for lots only seen through their calls
+    pub spaces: Option<(u32, u32)>,
+    /// Seconds a call stays parked before it goes back
+    pub timeout: u64,
+    /// Parked calls keyed by space
+    pub calls: BTreeMap<String, ParkedCall>
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct ParkedCall {
+    pub space: String,
+    pub channel: String,
+    pub caller: String,
+    pub caller_name: String,
+    /// Who parked it, e.g. `PJSIP/2003`
+    pub parker: String,
+    /// Unix timestamp at which the call was parked
+    pub parked: u64,
+    /// Unix timestamp at which the call goes back to `parker`
+    pub timeout: u64
+}
+
+impl ParkingLot {
+    /// Free spaces, when the range is known.
+    pub fn free(&self) -> Option<usize> {
+        self.spaces.map(|(start, stop)| (stop.saturating_sub(start) as usize + 1).saturating_sub(self.calls.len()))
+    }
+}
+
+/// Updates `parking` from the `Parkinglots` and `ParkedCalls` replies and the parking events;
+/// other frames are ignored.
+pub fn treat_parking(val: &str, parking: &Parking) {
+    match val.as_bytes() {
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'P', b'a', b'r', b'k', b'i', b'n', b'g', b'l', b'o', b't', b'\r', ..] => {
+            let v = get_map(val);
+            let Some(name) = v.get("Name") else { return };
+            let space = |key: &str| v.get(key).and_then(|x| x.parse().ok());
+            let mut parking = parking.lock().unwrap();
+            let lot = parking.entry(name.to_string()).or_default();
+
+            lot.name = name.to_string();
+            lot.spaces = space("StartSpace").zip(space("StopSpace"));
+            lot.timeout = v.get("Timeout").and_then(|x| x.parse().ok()).unwrap_or_default();
+        },
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'P', b'a', b'r', b'k', b'e', b'd', b'C', b'a', b'l', b'l', b'\r', ..] |
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'P', b'a', b'r', b'k', b'e', b'd', b'C', b'a', b'l', b'l', b'S', b'w', b'a', b'p', b'\r', ..] => {
+            let v = get_map(val);
+            let (Some(name), Some(space)) = (v.get("Parkinglot"), v.get("ParkingSpace")) else { return };
+            let get = |key: &str| v.get(key).unwrap_or(&"").to_string();
+            let seconds = |key: &str| v.get(key).and_then(|x| x.parse::<u64>().ok()).unwrap_or_default();
+            let parked = unix_now().saturating_sub(seconds("ParkingDuration"));
+            let mut parking = parking.lock().unwrap();
+            let lot = parking.entry(name.to_string()).or_default();
+
+            lot.name = name.to_string();
+            lot.calls.insert(space.to_string(), ParkedCall {
+                space: space.to_string(),
+                channel: get("ParkeeChannel"),
+                caller: get("ParkeeCallerIDNum"),
+                caller_name: get("ParkeeCallerIDName"),
+                parker: get("ParkerDialString"),
+                parked,
+                // ParkingTimeout is what is left, not the total
+                timeout: unix_now() + seconds("ParkingTimeout")
+            });
+        },
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'U', b'n', b'P', b'a', b'r', b'k', b'e', b'd', b'C', b'a', b'l', b'l', b'\r', ..] |
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'P', b'a', b'r', b'k', b'e', b'd', b'C', b'a', b'l', b'l', b'T', b'i', b'm', b'e', b'O', b'u', b't', b'\r', ..] |
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'P', b'a', b'r', b'k', b'e', b'd', b'C', b'a', b'l', b'l', b'G', b'i', b'v', b'e', b'U', b'p', b'\r', ..] => {
+            let v = get_map(val);
+            let (Some(name), Some(space)) = (v.get("Parkinglot"), v.get("ParkingSpace")) else { return };
+
+            if let Some(lot) = parking.lock().unwrap().get_mut(*name) {
+                lot.calls.remove(*space);
+            }
+        },
+        _ => ()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parked_calls_come_and_go() {
+        let parking = Parking::default();
+
+        treat_parking("Event: Parkinglot\r\nName: default\r\nStartSpace: 701\r\nStopSpace: 720\r\nTimeout: 45\r\n", &parking);
+        treat_parking("Event: ParkedCall\r\nParkeeChannel: PJSIP/2003-00000001\r\nParkeeCallerIDNum: 5551234\r\nParkeeCallerIDName: Maria\r\nParkerDialString: PJSIP/2004\r\nParkinglot: default\r\nParkingSpace: 701\r\nParkingTimeout: 30\r\nParkingDuration: 15\r\n", &parking);
+        treat_parking("Event: ParkedCall\r\nParkeeChannel: PJSIP/2005-00000002\r\nParkinglot: default\r\nParkingSpace: 702\r\nParkingTimeout: 45\r\nParkingDuration: 0\r\n", &parking);
+
+        {
+            let parking = parking.lock().unwrap();
+            let lot = &parking["default"];
+            let call = &lot.calls["701"];
+            let now = unix_now();
+
+            assert_eq!((lot.spaces, lot.timeout, lot.free()), (Some((701, 720)), 45, Some(18)));
+            assert_eq!((&call.caller[..], &call.caller_name[..], &call.parker[..]), ("5551234", "Maria", "PJSIP/2004"));
+            assert!((now - 16..=now - 15).contains(&call.parked));
+            // The timeout counts down from now, not from when it was parked
+            assert!((now + 30..=now + 31).contains(&call.timeout));
+        }
+
+        treat_parking("Event: UnParkedCall\r\nParkinglot: default\r\nParkingSpace: 701\r\n", &parking);
+        treat_parking("Event: ParkedCallTimeOut\r\nParkinglot: default\r\nParkingSpace: 702\r\n", &parking);
+        treat_parking("Event: UnParkedCall\r\nParkinglot: other\r\nParkingSpace: 701\r\n", &parking);
+
+        let parking = parking.lock().unwrap();
+        assert_eq!(parking.len(), 1);
+        assert!(parking["default"].calls.is_empty());
+        assert_eq!(parking["default"].free(), Some(20));
+    }
+
+    #[test]
+    fn lots_seen_through_calls_have_no_range() {
+        let parking = Parking::default();
+
+        treat_parking("Event: ParkedCall\r\nParkinglot: sales\r\nParkingSpace: 801\r\n", &parking);
+
+        assert_eq!(parking.lock().unwrap()["sales"].free(), None);
+    }
+}
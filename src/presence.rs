@@ -0,0 +1,159 @@
+use crate::*;
+
+/// Events the presence indicator consumes (class `call`).
+pub const PRESENCE_EVENTS: &[&str] = &["PresenceStateChange"];
+
+/// Presence set by the user or the softphone, independent of the device state.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PresenceKind {
+    NotSet,
+    Available,
+    Unavailable,
+    Away,
+    /// Extended away
+    Xa,
+    Chat,
+    Dnd,
+    Invalid,
+    #[default]
+    Unknown
+}
+
+impl From<&str> for PresenceKind {
+    fn from(val: &str) -> Self {
+        match &val.to_lowercase()[..] {
+            "not_set" => Self::NotSet,
+            "available" => Self::Available,
+            "unavailable" => Self::Unavailable,
+            "away" => Self::Away,
+            "xa" => Self::Xa,
+            "chat" => Self::Chat,
+            "dnd" => Self::Dnd,
+            "invalid" => Self::Invalid,
+            _ => Self::Unknown
+        }
+    }
+}
+
+impl Display for PresenceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotSet => write!(f, "Not set"),
+            Self::Xa => write!(f, "Extended away"),
+            Self::Dnd => write!(f, "DND"),
+            other => write!(f, "{other:?}")
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Presence {
+    /// Presence part of the hint, e.g. `CustomPresence:2003`
+    pub provider: String,
+    pub state: PresenceKind,
+    pub subtype: String,
+    pub message: String
+}
+
+impl Presence {
+    /// From the `Hint` of an extension, `PJSIP/2003&Custom:DND2003,CustomPresence:2003`; `None` without a presence provider.
+    pub fn from_hint(hint: &str) -> Option<Self> {
+        let provider = hint.split_once(',')?.1.trim();
+
+        (!provider.is_empty()).then(|| Self { provider: provider.to_owned(), ..Default::default() })
+    }
+
+    /// Whether there's something worth showing besides the device state.
+    pub fn is_set(&self) -> bool {
+        !matches!(self.state, PresenceKind::NotSet | PresenceKind::Invalid | PresenceKind::Unknown)
+    }
+
+    /// Takes the state of a `PresenceState` reply or a `PresenceStateChange` event.
+    pub fn update(&mut self, val: &str) {
+        let v = get_map(val);
+
+        self.state = v.get("State").or_else(|| v.get("Status")).copied().unwrap_or_default().into();
+        self.subtype = v.get("Subtype").unwrap_or(&"").to_string();
+        // The reply has two `Message` headers, the first one always saying `Presence State`
+        self.message = val.lines()
+            .filter_map(|x| x.strip_prefix("Message: "))
+            .filter(|x| *x != "Presence State")
+            .last()
+            .unwrap_or_default()
+            .to_owned();
+    }
+}
+
+/// Updates the presence of the extensions sharing the provider of a `PresenceStateChange` and sends
+/// the change of each one that moved to `send`; other frames are ignored.
+pub fn treat_presence(val: &str, send: &Sender<Message>, data: &Mutex<BTreeMap<String, SipStatus>>) {
+    let [b'E', b'v', b'e', b'n', b't', b':', b' ', b'P', b'r', b'e', b's', b'e', b'n', b'c', b'e', b'S', b't', b'a', b't', b'e', b'C', b'h', b'a', b'n', b'g', b'e', b'\r', ..] = val.as_bytes() else { return };
+    let v = get_map(val);
+    let Some(presentity) = v.get("Presentity") else { return };
+
+    for (exten, entry) in data.lock().unwrap().iter_mut() {
+        let Some(presence) = entry.presence.as_mut().filter(|x| x.provider == *presentity) else { continue };
+        let before = presence.clone();
+
+        presence.update(val);
+        if *presence != before {
+            let mut old = entry.clone();
+            old.presence = Some(before);
+            // As in `treat`, nobody may be listening anymore
+            let _ = send.send(Message::Updated(StatusChange { id: 0, pbx: String::new(), exten: exten.clone(), old, new: entry.clone(), at: unix_now() }));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(hint: &str) -> SipStatus {
+        SipStatus { status: 0, status_text: "Idle".to_owned(), presence: Presence::from_hint(hint) }
+    }
+
+    #[test]
+    fn provider_comes_from_the_hint() {
+        assert_eq!(Presence::from_hint("PJSIP/2003&Custom:DND2003,CustomPresence:2003").unwrap().provider, "CustomPresence:2003");
+        assert_eq!(Presence::from_hint("PJSIP/2003"), None);
+        assert_eq!(Presence::from_hint("PJSIP/2003,"), None);
+    }
+
+    #[test]
+    fn update_takes_the_reply_and_the_event() {
+        let mut presence = Presence::from_hint("PJSIP/2003,CustomPresence:2003").unwrap();
+
+        presence.update("Response: Success\r\nMessage: Presence State\r\nState: away\r\nSubtype: lunch\r\nMessage: Back at 2\r\n");
+        assert_eq!((presence.state, &presence.subtype[..], &presence.message[..]), (PresenceKind::Away, "lunch", "Back at 2"));
+        assert!(presence.is_set());
+
+        presence.update("Event: PresenceStateChange\r\nPresentity: CustomPresence:2003\r\nStatus: not_set\r\n");
+        assert_eq!((presence.state, &presence.subtype[..], &presence.message[..]), (PresenceKind::NotSet, "", ""));
+        assert!(!presence.is_set());
+    }
+
+    #[test]
+    fn changes_are_sent() {
+        let (send, recv) = mpsc::channel();
+        let data = Mutex::new(BTreeMap::from([
+            ("2003".to_owned(), status("PJSIP/2003,CustomPresence:2003")),
+            ("2004".to_owned(), status("PJSIP/2004,CustomPresence:2004")),
+            ("2005".to_owned(), status("PJSIP/2005"))
+        ]));
+        let event = "Event: PresenceStateChange\r\nPresentity: CustomPresence:2003\r\nStatus: dnd\r\nSubtype: \r\nMessage: Meeting\r\n";
+
+        treat_presence(event, &send, &data);
+        // Same state again, nothing moved
+        treat_presence(event, &send, &data);
+        drop(send);
+
+        let changes: Vec<_> = recv.iter().map(|x| match x { Message::Updated(change) => change, _ => panic!("not a change") }).collect();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].exten, "2003");
+        assert_eq!(changes[0].old.presence.as_ref().unwrap().state, PresenceKind::Unknown);
+        assert_eq!(changes[0].new.presence.as_ref().unwrap().state, PresenceKind::Dnd);
+        assert_eq!(data.lock().unwrap()["2004"].presence.as_ref().unwrap().state, PresenceKind::Unknown);
+    }
+}
@@ -0,0 +1,76 @@
+use crate::*;
+
+/// Events the queue tracking consumes (class `agent`).
+pub const QUEUE_EVENTS: &[&str] = &["QueueCallerJoin", "QueueCallerLeave", "QueueCallerAbandon"];
+
+pub type Queues = Arc<Mutex<BTreeMap<String, QueueStats>>>;
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct QueueStats {
+    /// Unix timestamp at which each waiting caller joined, keyed by `Uniqueid`
+    pub callers: BTreeMap<String, u64>
+}
+
+impl QueueStats {
+    /// Seconds the oldest caller has been waiting.
+    pub fn longest_wait(&self) -> u64 {
+        self.callers.values().min().map(|x| unix_now().saturating_sub(*x)).unwrap_or_default()
+    }
+}
+
+/// Tracks callers waiting in each queue; only callers that join after login are seen.
+pub fn treat_queues(val: &str, data: &Mutex<BTreeMap<String, QueueStats>>) {
+    match val.as_bytes() {
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'Q', b'u', b'e', b'u', b'e', b'C', b'a', b'l', b'l', b'e', b'r', b'J', b'o', b'i', b'n', ..] => {
+            let v = get_map(val);
+            let (Some(queue), Some(id)) = (v.get("Queue"), v.get("Uniqueid")) else { return };
+
+            data.lock().unwrap().entry(queue.to_string()).or_default()
+                .callers.insert(id.to_string(), unix_now());
+        },
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'Q', b'u', b'e', b'u', b'e', b'C', b'a', b'l', b'l', b'e', b'r', b'L', b'e', b'a', b'v', b'e', ..] |
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'Q', b'u', b'e', b'u', b'e', b'C', b'a', b'l', b'l', b'e', b'r', b'A', b'b', b'a', b'n', b'd', b'o', b'n', ..] => {
+            let v = get_map(val);
+            let (Some(queue), Some(id)) = (v.get("Queue"), v.get("Uniqueid")) else { return };
+
+            if let Some(stats) = data.lock().unwrap().get_mut(*queue) {
+                stats.callers.remove(*id);
+            }
+        },
+        _ => ()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follows_the_waiting_callers() {
+        let data = Mutex::new(BTreeMap::new());
+
+        treat_queues("Event: QueueCallerJoin\r\nQueue: sales\r\nUniqueid: 1700000000.1\r\nPosition: 1\r\n", &data);
+        treat_queues("Event: QueueCallerJoin\r\nQueue: sales\r\nUniqueid: 1700000000.2\r\nPosition: 2\r\n", &data);
+        treat_queues("Event: QueueCallerJoin\r\nQueue: support\r\nUniqueid: 1700000000.3\r\nPosition: 1\r\n", &data);
+        // Without the caller there's nothing to track
+        treat_queues("Event: QueueCallerJoin\r\nQueue: sales\r\n", &data);
+        assert_eq!(data.lock().unwrap()["sales"].callers.len(), 2);
+
+        treat_queues("Event: QueueCallerLeave\r\nQueue: sales\r\nUniqueid: 1700000000.1\r\n", &data);
+        treat_queues("Event: QueueCallerAbandon\r\nQueue: support\r\nUniqueid: 1700000000.3\r\n", &data);
+        treat_queues("Event: QueueCallerLeave\r\nQueue: unknown\r\nUniqueid: 1700000000.2\r\n", &data);
+
+        let data = data.lock().unwrap();
+        assert_eq!(data["sales"].callers.keys().collect::<Vec<_>>(), ["1700000000.2"]);
+        assert!(data["support"].callers.is_empty());
+        assert!(!data.contains_key("unknown"));
+    }
+
+    #[test]
+    fn longest_wait_is_the_oldest_caller() {
+        let stats = QueueStats { callers: BTreeMap::from([("a".to_owned(), unix_now() - 90), ("b".to_owned(), unix_now() - 10)]) };
+
+        assert!((90..=91).contains(&stats.longest_wait()));
+        assert_eq!(QueueStats::default().longest_wait(), 0);
+    }
+}
@@ -0,0 +1,180 @@
+use crate::*;
+
+/// Events the registrations view consumes, on top of `STATUS_EVENTS`.
+pub const CONTACT_EVENTS: &[&str] = &["ContactStatus"];
+/// An RTT above the average times this factor is flagged as a spike.
+const RTT_SPIKE_FACTOR: f64 = 3.;
+/// Jitter below this (in microseconds) is never flagged, however small the average is.
+const RTT_SPIKE_FLOOR: f64 = 20_000.;
+
+/// Registered contacts keyed by their URI.
+pub type Registrations = Arc<Mutex<BTreeMap<String, Registration>>>;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum Reachability {
+    Reachable,
+    Unreachable,
+    NonQualified,
+    #[default]
+    Unknown
+}
+
+impl From<&str> for Reachability {
+    fn from(val: &str) -> Self {
+        match val {
+            "Reachable" | "Avail" => Self::Reachable,
+            "Unreachable" | "Unavail" => Self::Unreachable,
+            "NonQualified" => Self::NonQualified,
+            _ => Self::Unknown
+        }
+    }
+}
+
+impl Display for Reachability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct Registration {
+    pub aor: String,
+    pub uri: String,
+    pub user_agent: String,
+    pub status: Reachability,
+    /// Last qualify round trip, in microseconds
+    pub rtt: Option<u64>,
+    /// Moving average of the qualify round trip, in microseconds
+    pub rtt_avg: Option<f64>,
+    pub rtt_spike: bool,
+    /// Unix timestamp of the registration expiration
+    pub expiration: u64
+}
+
+impl Registration {
+    /// Whether the device needs attention: unreachable or with a qualify RTT spike.
+    pub fn flagged(&self) -> bool {
+        self.status == Reachability::Unreachable || self.rtt_spike
+    }
+
+    /// Seconds until the registration expires, zero if already expired.
+    pub fn expires_in(&self) -> u64 {
+        self.expiration.saturating_sub(unix_now())
+    }
+
+    pub fn rtt_text(&self) -> String {
+        self.rtt.map(|x| format!("{:.1} ms", x as f64 / 1000.)).unwrap_or_else(|| "-".to_owned())
+    }
+
+    fn set_rtt(&mut self, rtt: Option<u64>) {
+        let Some(rtt) = rtt else { return };
+        let avg = self.rtt_avg.unwrap_or(rtt as f64);
+
+        self.rtt = Some(rtt);
+        self.rtt_spike = rtt as f64 > avg * RTT_SPIKE_FACTOR && rtt as f64 - avg > RTT_SPIKE_FLOOR;
+        self.rtt_avg = Some(avg * 0.8 + rtt as f64 * 0.2);
+    }
+}
+
+/// Updates `data` from `ContactList` and `ContactStatus` events; other frames are ignored.
+pub fn treat_registrations(val: &str, data: &Mutex<BTreeMap<String, Registration>>) {
+    match val.as_bytes() {
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'C', b'o', b'n', b't', b'a', b'c', b't', b'L', b'i', b's', b't', b'C', b'o', b'm', b'p', b'l', b'e', b't', b'e', ..] => (),
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'C', b'o', b'n', b't', b'a', b'c', b't', b'L', b'i', b's', b't', ..] => {
+            let v = get_map(val);
+            let Some(uri) = v.get("Uri") else { return };
+            let mut data = data.lock().unwrap();
+            let reg = data.entry(uri.to_string()).or_default();
+
+            reg.uri = uri.to_string();
+            reg.aor = v.get("ObjectName").and_then(|x| x.split(";@").next()).unwrap_or_default().to_owned();
+            reg.user_agent = v.get("UserAgent").unwrap_or(&"").to_string();
+            reg.status = v.get("Status").copied().unwrap_or_default().into();
+            reg.expiration = v.get("ExpirationTime").and_then(|x| x.parse().ok()).unwrap_or_default();
+            reg.set_rtt(v.get("RoundtripUsec").and_then(|x| x.parse().ok()));
+        },
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'C', b'o', b'n', b't', b'a', b'c', b't', b'S', b't', b'a', b't', b'u', b's', ..] => {
+            let v = get_map(val);
+            // ContactStatusDetail shares the prefix but has no ContactStatus header
+            let (Some(uri), Some(status)) = (v.get("URI"), v.get("ContactStatus")) else { return };
+            let mut data = data.lock().unwrap();
+
+            match *status {
+                "Removed" => {
+                    data.remove(*uri);
+                },
+                status => {
+                    let reg = data.entry(uri.to_string()).or_default();
+
+                    reg.uri = uri.to_string();
+                    if let Some(aor) = v.get("AOR") {
+                        reg.aor = aor.to_string();
+                    }
+                    if let Some(user_agent) = v.get("UserAgent") {
+                        reg.user_agent = user_agent.to_string();
+                    }
+                    if let Some(expiration) = v.get("RegExpire").and_then(|x| x.parse().ok()) {
+                        reg.expiration = expiration;
+                    }
+                    // Created and Updated only refresh the registration, not its reachability
+                    if !matches!(status, "Created" | "Updated") {
+                        reg.status = status.into();
+                    }
+                    reg.set_rtt(v.get("RoundtripUsec").and_then(|x| x.parse().ok()));
+                }
+            }
+        },
+        _ => ()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rtt_spikes_are_flagged() {
+        let mut reg = Registration { status: Reachability::Reachable, ..Default::default() };
+
+        // The first sample is the average
+        reg.set_rtt(Some(2_000));
+        assert!(!reg.flagged());
+        // Three times the average, but only a few milliseconds of jitter
+        reg.set_rtt(Some(8_000));
+        assert!(!reg.flagged());
+        reg.set_rtt(Some(80_000));
+        assert!(reg.flagged());
+        // A missing RTT keeps the last one
+        reg.set_rtt(None);
+        assert_eq!(reg.rtt, Some(80_000));
+        assert!(reg.flagged());
+        reg.set_rtt(Some(3_000));
+        assert!(!reg.flagged());
+    }
+
+    #[test]
+    fn follows_the_contact_events() {
+        let data = Mutex::new(BTreeMap::new());
+        let uri = "sip:2003@10.0.0.5:5060";
+
+        treat_registrations(&format!("Event: ContactList\r\nObjectName: 2003;@abc\r\nUri: {uri}\r\nUserAgent: Yealink\r\nStatus: Reachable\r\nRoundtripUsec: 2000\r\nExpirationTime: 1700000000\r\n"), &data);
+        treat_registrations(&format!("Event: ContactStatus\r\nURI: {uri}\r\nContactStatus: Updated\r\nAOR: 2003\r\nRegExpire: 1700000600\r\nRoundtripUsec: 90000\r\n"), &data);
+
+        {
+            let data = data.lock().unwrap();
+            let reg = &data[uri];
+
+            assert_eq!((&reg.aor[..], &reg.user_agent[..], reg.status, reg.expiration), ("2003", "Yealink", Reachability::Reachable, 1700000600));
+            assert!(reg.rtt_spike);
+        }
+
+        treat_registrations(&format!("Event: ContactStatus\r\nURI: {uri}\r\nContactStatus: Unreachable\r\nAOR: 2003\r\n"), &data);
+        assert!(data.lock().unwrap()[uri].flagged());
+        // No ContactStatus header, not a status change
+        treat_registrations(&format!("Event: ContactStatusDetail\r\nURI: {uri}\r\nStatus: Reachable\r\n"), &data);
+        assert_eq!(data.lock().unwrap()[uri].status, Reachability::Unreachable);
+
+        treat_registrations(&format!("Event: ContactStatus\r\nURI: {uri}\r\nContactStatus: Removed\r\n"), &data);
+        assert!(data.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,337 @@
+use crate::*;
+use std::time::Instant;
+
+/// Alerts currently firing, for the GUI banner.
+pub type Alerts = Arc<Mutex<Vec<Alert>>>;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Rule {
+    pub name: String,
+    pub condition: Condition,
+    /// Seconds the condition must hold before the alert fires
+    #[serde(default)]
+    pub for_secs: u64,
+    /// Seconds the condition must stay false before the alert clears
+    #[serde(default)]
+    pub clear_secs: u64,
+    #[serde(default)]
+    pub severity: Severity
+}
+
+/// Subjects left as `None` match every extension, AOR or queue, each one alerting on its own.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Condition {
+    /// Extension in one of the given `ExtensionStatus` codes
+    ExtensionStatus {
+        exten: Option<String>,
+        status: Vec<i8>
+    },
+    /// More than `above` extensions without a reachable contact, clearing at `clear_at` or less
+    Unregistered {
+        above: usize,
+        clear_at: Option<usize>
+    },
+    /// AOR with an unreachable contact, phones and trunks alike
+    Unreachable {
+        aor: Option<String>
+    },
+    /// Oldest caller waiting more than `above` seconds, clearing at `clear_at` or less
+    QueueWait {
+        queue: Option<String>,
+        above: u64,
+        clear_at: Option<u64>
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Info,
+    #[default]
+    Warning,
+    Critical
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertState {
+    Firing,
+    Resolved
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Alert {
+    pub rule: String,
+    pub subject: String,
+    pub severity: Severity,
+    pub state: AlertState,
+    pub message: String,
+    /// Unix timestamp of the transition
+    pub at: u64
+}
+
+pub struct Snapshot<'a> {
+    pub extensions: &'a BTreeMap<String, SipStatus>,
+    pub registrations: &'a BTreeMap<String, Registration>,
+    pub queues: &'a BTreeMap<String, QueueStats>
+}
+
+impl Condition {
+    /// Whether the condition holds for each subject. Subjects already alerting are given
+    /// by `firing`, so the clear threshold applies to them instead of the firing one.
+    fn evaluate(&self, snap: &Snapshot, firing: impl Fn(&str) -> bool) -> Vec<(String, bool, String)> {
+        let matches = |filter: &Option<String>, subject: &str| filter.as_deref().map_or(true, |x| x == subject);
+
+        match self {
+            Self::ExtensionStatus { exten, status } => snap.extensions.iter()
+                .filter(|(k, _)| matches(exten, k))
+                .map(|(k, v)| (k.clone(), status.contains(&v.status), format!("{k} is {}", v.status_text)))
+                .collect(),
+            Self::Unregistered { above, clear_at } => {
+                let count = snap.extensions.keys()
+                    .filter(|k| !snap.registrations.values().any(|r| r.aor == **k && r.status != Reachability::Unreachable))
+                    .count();
+                let limit = if firing("") { clear_at.unwrap_or(*above) } else { *above };
+
+                vec![(String::new(), count > limit, format!("{count} extensions unregistered"))]
+            },
+            Self::Unreachable { aor } => {
+                let mut aors = BTreeMap::<&str, bool>::new();
+
+                for reg in snap.registrations.values().filter(|r| matches(aor, &r.aor)) {
+                    *aors.entry(&reg.aor).or_default() |= reg.status == Reachability::Unreachable;
+                }
+
+                aors.into_iter().map(|(k, v)| (k.to_owned(), v, format!("{k} is unreachable"))).collect()
+            },
+            Self::QueueWait { queue, above, clear_at } => snap.queues.iter()
+                .filter(|(k, _)| matches(queue, k))
+                .map(|(k, v)| {
+                    let wait = v.longest_wait();
+                    let limit = if firing(k) { clear_at.unwrap_or(*above) } else { *above };
+
+                    (k.clone(), wait > limit, format!("queue {k} waiting for {wait}s"))
+                })
+                .collect()
+        }
+    }
+}
+
+#[derive(Default)]
+struct RuleState {
+    firing: bool,
+    /// When the condition started to disagree with `firing`
+    since: Option<Instant>
+}
+
+pub struct RuleEngine {
+    rules: Vec<Rule>,
+    states: HashMap<(usize, String), RuleState>,
+    active: Alerts,
+    subscribers: Vec<Sender<Alert>>
+}
+
+impl RuleEngine {
+    pub fn new(rules: Vec<Rule>) -> Self {
+        Self {
+            rules,
+            states: HashMap::new(),
+            active: Default::default(),
+            subscribers: Vec::new()
+        }
+    }
+
+    /// Receives every alert transition, firing and resolved.
+    pub fn subscribe(&mut self) -> Receiver<Alert> {
+        let (send, recv) = mpsc::channel();
+        self.subscribers.push(send);
+
+        recv
+    }
+
+    pub fn active(&self) -> Alerts {
+        Arc::clone(&self.active)
+    }
+
+    /// Evaluates every rule, applying the debounce (`for_secs`) and clear delay (`clear_secs`).
+    pub fn tick(&mut self, snap: &Snapshot, now: Instant) {
+        let mut alerts = Vec::new();
+
+        for (idx, rule) in self.rules.iter().enumerate() {
+            let results = rule.condition.evaluate(snap, |subject| {
+                self.states.get(&(idx, subject.to_owned())).map_or(false, |x| x.firing)
+            });
+            let mut seen = Vec::with_capacity(results.len());
+
+            for (subject, holds, message) in results {
+                let state = self.states.entry((idx, subject.clone())).or_default();
+
+                if holds == state.firing {
+                    state.since = None;
+                } else {
+                    let since = *state.since.get_or_insert(now);
+                    let delay = if holds { rule.for_secs } else { rule.clear_secs };
+
+                    if now.duration_since(since).as_secs() >= delay {
+                        state.firing = holds;
+                        state.since = None;
+                        alerts.push(Alert {
+                            rule: rule.name.clone(),
+                            subject: subject.clone(),
+                            severity: rule.severity,
+                            state: if holds { AlertState::Firing } else { AlertState::Resolved },
+                            message,
+                            at: unix_now()
+                        });
+                    }
+                }
+
+                seen.push(subject);
+            }
+
+            // Subjects that disappeared (extension removed, queue emptied) resolve right away
+            for ((_, subject), state) in self.states.iter_mut().filter(|((i, s), _)| *i == idx && !seen.contains(s)) {
+                if state.firing {
+                    state.firing = false;
+                    alerts.push(Alert {
+                        rule: rule.name.clone(),
+                        subject: subject.clone(),
+                        severity: rule.severity,
+                        state: AlertState::Resolved,
+                        message: format!("{subject} is gone"),
+                        at: unix_now()
+                    });
+                }
+            }
+        }
+
+        if alerts.is_empty() {
+            return
+        }
+
+        let mut active = self.active.lock().unwrap();
+
+        for alert in alerts {
+            active.retain(|x| x.rule != alert.rule || x.subject != alert.subject);
+            if alert.state == AlertState::Firing {
+                active.push(alert.clone());
+            }
+
+            self.subscribers.retain(|x| x.send(alert.clone()).is_ok());
+        }
+    }
+
+    /// Evaluates the rules once per second on a background thread.
+    pub fn spawn(mut self, data: AllData, regs: Registrations, queues: Queues) {
+        thread::spawn(move || loop {
+            {
+                let (extensions, registrations, queues) = (data.lock().unwrap(), regs.lock().unwrap(), queues.lock().unwrap());
+                let snap = Snapshot { extensions: &extensions, registrations: &registrations, queues: &queues };
+
+                self.tick(&snap, Instant::now());
+            }
+
+            thread::sleep(Duration::from_secs(1));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extensions(status: i8) -> BTreeMap<String, SipStatus> {
+        BTreeMap::from([("2003".to_owned(), SipStatus { status, status_text: String::new(), presence: None })])
+    }
+
+    fn queue(wait: u64) -> BTreeMap<String, QueueStats> {
+        let stats = QueueStats { callers: BTreeMap::from([("1700000000.1".to_owned(), unix_now() - wait)]) };
+
+        BTreeMap::from([("sales".to_owned(), stats)])
+    }
+
+    /// Ticks `engine` with the status of 2003 and the longest wait of the `sales` queue.
+    fn tick(engine: &mut RuleEngine, status: i8, wait: u64, now: Instant) {
+        let (extensions, queues) = (extensions(status), queue(wait));
+
+        engine.tick(&Snapshot { extensions: &extensions, registrations: &BTreeMap::new(), queues: &queues }, now);
+    }
+
+    fn rule(condition: Condition, for_secs: u64, clear_secs: u64) -> Rule {
+        Rule { name: "test".to_owned(), condition, for_secs, clear_secs, severity: Severity::Critical }
+    }
+
+    #[test]
+    fn fires_only_when_the_condition_holds_for_secs() {
+        let unavailable = Condition::ExtensionStatus { exten: Some("2003".to_owned()), status: vec![4] };
+        let mut engine = RuleEngine::new(vec![rule(unavailable, 10, 5)]);
+        let alerts = engine.subscribe();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        // A blip shorter than for_secs
+        tick(&mut engine, 4, 0, at(0));
+        tick(&mut engine, 4, 0, at(9));
+        tick(&mut engine, 0, 0, at(10));
+        tick(&mut engine, 4, 0, at(11));
+        tick(&mut engine, 4, 0, at(20));
+        assert!(alerts.try_recv().is_err());
+        assert!(engine.active().lock().unwrap().is_empty());
+
+        tick(&mut engine, 4, 0, at(21));
+        let alert = alerts.try_recv().unwrap();
+        assert_eq!((&alert.subject[..], alert.state, alert.severity), ("2003", AlertState::Firing, Severity::Critical));
+        assert_eq!(engine.active().lock().unwrap().len(), 1);
+
+        // Clears only after clear_secs without the condition
+        tick(&mut engine, 0, 0, at(30));
+        tick(&mut engine, 0, 0, at(34));
+        assert!(alerts.try_recv().is_err());
+        tick(&mut engine, 0, 0, at(35));
+        assert_eq!(alerts.try_recv().unwrap().state, AlertState::Resolved);
+        assert!(engine.active().lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn queue_wait_does_not_flap_between_the_thresholds() {
+        let wait = Condition::QueueWait { queue: None, above: 60, clear_at: Some(30) };
+        let mut engine = RuleEngine::new(vec![rule(wait, 0, 0)]);
+        let alerts = engine.subscribe();
+        let now = Instant::now();
+
+        tick(&mut engine, 0, 50, now);
+        assert!(alerts.try_recv().is_err());
+        tick(&mut engine, 0, 70, now);
+        assert_eq!(alerts.try_recv().unwrap().state, AlertState::Firing);
+
+        // Below the threshold but above clear_at, still firing
+        tick(&mut engine, 0, 50, now);
+        tick(&mut engine, 0, 70, now);
+        tick(&mut engine, 0, 40, now);
+        assert!(alerts.try_recv().is_err());
+        assert_eq!(engine.active().lock().unwrap().len(), 1);
+
+        tick(&mut engine, 0, 20, now);
+        assert_eq!(alerts.try_recv().unwrap().state, AlertState::Resolved);
+        // Back under the firing threshold once resolved
+        tick(&mut engine, 0, 50, now);
+        assert!(alerts.try_recv().is_err());
+    }
+
+    #[test]
+    fn gone_subjects_resolve() {
+        let busy = Condition::ExtensionStatus { exten: None, status: vec![2] };
+        let mut engine = RuleEngine::new(vec![rule(busy, 0, 60)]);
+        let alerts = engine.subscribe();
+        let now = Instant::now();
+
+        tick(&mut engine, 2, 0, now);
+        assert_eq!(alerts.try_recv().unwrap().state, AlertState::Firing);
+
+        engine.tick(&Snapshot { extensions: &BTreeMap::new(), registrations: &BTreeMap::new(), queues: &BTreeMap::new() }, now);
+        let alert = alerts.try_recv().unwrap();
+        assert_eq!((alert.state, &alert.message[..]), (AlertState::Resolved, "2003 is gone"));
+        assert!(engine.active().lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,140 @@
+use crate::*;
+use std::{
+    fs,
+    path::Path
+};
+
+/// Read from the working directory at startup.
+pub const SETTINGS_FILE: &str = "sip_monitor.json";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Session used by the bridge for its own outputs (webhooks, metrics, ...)
+    pub ami: Option<AmiSettings>,
+    /// `ExtensionState` requests the bootstrap keeps in flight
+    pub bootstrap_window: usize,
+    /// Address the bridge listens on. Nothing on it asks for credentials, `0.0.0.0` opens it to the whole network
+    pub http_bind: String,
+    /// Port of the bridge: web client, `/ws`, `/health`, `/metrics`, `/api/...` and `/events`
+    pub http_port: u16,
+    /// Serves the web client from this directory instead of the copy embedded in the binary
+    pub web_dir: Option<String>,
+    /// CSV or LDIF file with names, departments, locations and tags of the extensions
+    pub directory: Option<String>,
+    /// Trunks to watch besides the ones that register, and their channel limits
+    pub trunks: Vec<TrunkSettings>,
+    /// AMI users allowed to change DND and forwarding, and to use the operator mode of the dashboard
+    pub operators: Vec<String>,
+    /// Extension of the operator, rung by click-to-dial and pickup
+    pub operator_exten: Option<String>,
+    /// Context of the calls and transfers made from the dashboard
+    pub dial_context: String,
+    pub rules: Vec<Rule>,
+    pub webhooks: Vec<Webhook>,
+    /// Plays a capture instead of connecting to `ami`
+    pub replay: Option<ReplaySettings>,
+    /// Runs the native app as a full screen wallboard, logged in with `ami`
+    pub wallboard: Option<WallboardSettings>,
+    /// Tile colors: one of `themes`, or `classic`, `alternate`, `colorblind` or `high_contrast`
+    pub theme: String,
+    /// Dark or light look for everything but the tiles
+    pub dark: bool,
+    /// Themes besides the built in ones
+    pub themes: Vec<Theme>
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            ami: None,
+            bootstrap_window: BOOTSTRAP_WINDOW,
+            http_bind: "127.0.0.1".to_owned(),
+            http_port: 61339,
+            web_dir: None,
+            directory: None,
+            trunks: Vec::new(),
+            operators: Vec::new(),
+            operator_exten: None,
+            dial_context: "from-internal".to_owned(),
+            rules: Vec::new(),
+            webhooks: Vec::new(),
+            replay: None,
+            wallboard: None,
+            theme: DEFAULT_THEME.to_owned(),
+            dark: true,
+            themes: Vec::new()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AmiSettings {
+    pub addr: String,
+    pub user: String,
+    pub pass: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// Capture file recording every frame of the session
+    #[serde(default)]
+    pub record: Option<String>
+}
+
+fn default_port() -> u16 {
+    5038
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TrunkSettings {
+    /// PJSIP endpoint of the trunk
+    pub endpoint: String,
+    /// Channels the provider allows at once
+    #[serde(default)]
+    pub limit: Option<usize>
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReplaySettings {
+    pub file: String,
+    /// `1` keeps the original timing, `0` plays as fast as possible
+    #[serde(default = "default_speed")]
+    pub speed: f64
+}
+
+fn default_speed() -> f64 {
+    1.
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct WallboardSettings {
+    /// Seconds each page stays on screen, when the extensions don't fit in one
+    pub page_seconds: u64,
+    /// Tiles narrower than this, in points, are split into pages instead
+    pub min_tile_width: f32
+}
+
+impl Default for WallboardSettings {
+    fn default() -> Self {
+        Self {
+            page_seconds: 15,
+            min_tile_width: 160.
+        }
+    }
+}
+
+impl Settings {
+    pub fn load() -> Self {
+        Self::from_path(SETTINGS_FILE)
+    }
+
+    /// A missing file gives the defaults; an invalid one is reported and ignored.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        let Ok(val) = fs::read_to_string(path.as_ref()) else { return Self::default() };
+
+        serde_json::from_str(&val).unwrap_or_else(|e| {
+            elog!("Configuração inválida em {}: {e}", path.as_ref().display());
+            Self::default()
+        })
+    }
+}
@@ -0,0 +1,128 @@
+use crate::*;
+
+/// Comment sent while idle, so proxies keep the stream open and dead clients are noticed.
+const KEEPALIVE: Duration = Duration::from_secs(15);
+
+/// Streams the monitor as Server-Sent Events: a `snapshot` of every extension, then a
+/// `status` event per change and an `alert` event per alert transition.
+///
+/// A client resuming with `Last-Event-ID` gets the changes it missed instead of the
+/// snapshot, as long as they are still in the history.
+pub fn sse(monitor: &Arc<Monitor>, req: &Request) -> Response {
+    let last_id = req.headers.get("last-event-id").and_then(|x| x.parse::<u64>().ok());
+    let monitor = Arc::clone(monitor);
+
+    Response::stream("text/event-stream", move |stream| {
+        let events = monitor.subscribe();
+        let mut sent = {
+            let history = monitor.history.lock().unwrap();
+
+            match last_id {
+                Some(id) if history.has(id) => {
+                    for change in history.after(id) {
+                        send(stream, Some(change.id), "status", &change)?;
+                    }
+                },
+                _ => send(stream, Some(history.last_id()), "snapshot", &*monitor.data.lock().unwrap())?
+            }
+
+            history.last_id()
+        };
+
+        loop {
+            match events.recv_timeout(KEEPALIVE) {
+                Ok(MonitorEvent::Status(change)) if change.id > sent => {
+                    sent = change.id;
+                    send(stream, Some(change.id), "status", &change)?;
+                },
+                Ok(MonitorEvent::Status(_)) => (),
+                Ok(MonitorEvent::Alert(alert)) => send(stream, None, "alert", &alert)?,
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    stream.write_all(b": keepalive\n\n")?;
+                    stream.flush()?;
+                },
+                Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(())
+            }
+        }
+    })
+    .header("Cache-Control", "no-cache")
+    .header("X-Accel-Buffering", "no")
+}
+
+fn send<T: Serialize + ?Sized>(stream: &mut impl Write, id: Option<u64>, event: &str, data: &T) -> Result<()> {
+    let mut frame = String::new();
+
+    if let Some(id) = id {
+        frame += &format!("id: {id}\n");
+    }
+    frame += &format!("event: {event}\ndata: {}\n\n", serde_json::to_string(data)?);
+
+    stream.write_all(frame.as_bytes())?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::BufReader,
+        net::{TcpListener, TcpStream}
+    };
+
+    /// Runs the stream of `sse` and reads its first `count` events.
+    fn events(monitor: &Arc<Monitor>, last_id: Option<u64>, count: usize) -> Vec<String> {
+        let mut req = Request::default();
+        req.headers.extend(last_id.map(|x| ("last-event-id".to_owned(), x.to_string())));
+        let res = sse(monitor, &req);
+        let body = res.stream.unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (mut server, _) = listener.accept().unwrap();
+        thread::spawn(move || body(&mut server));
+
+        let mut reader = BufReader::new(client);
+        (0..count).map(|_| {
+            let mut event = String::new();
+            while !event.ends_with("\n\n") {
+                reader.read_line(&mut event).unwrap();
+            }
+            event
+        })
+        .collect()
+    }
+
+    #[test]
+    fn frames() {
+        let mut out = Vec::new();
+        send(&mut out, Some(7), "status", &["a"]).unwrap();
+        send(&mut out, None, "alert", "b").unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "id: 7\nevent: status\ndata: [\"a\"]\n\nevent: alert\ndata: \"b\"\n\n");
+    }
+
+    #[test]
+    fn resumes_from_the_history() {
+        let monitor = capture::replayed();
+        let ids = {
+            let mut history = monitor.history.lock().unwrap();
+            let change = history.iter().next().unwrap().clone();
+            history.push(change.clone());
+            history.push(change);
+
+            history.iter().map(|x| x.id).collect::<Vec<_>>()
+        };
+        let last = *ids.last().unwrap();
+
+        let resumed = events(&monitor, Some(ids[0]), 2);
+        for (event, id) in resumed.iter().zip(&ids[1..]) {
+            assert!(event.starts_with(&format!("id: {id}\nevent: status\ndata: {{")), "{event}");
+        }
+
+        // Too far ahead to be resumed, and no Last-Event-ID at all
+        for last_id in [Some(last + 1), None] {
+            let snapshot = &events(&monitor, last_id, 1)[0];
+            assert!(snapshot.starts_with(&format!("id: {last}\nevent: snapshot\ndata: {{\"2001\":")), "{snapshot}");
+        }
+    }
+}
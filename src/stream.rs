@@ -0,0 +1,145 @@
+use crate::*;
+use std::str::FromStr;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Ndjson,
+    Csv
+}
+
+impl FromStr for Format {
+    type Err = io::Error;
+
+    fn from_str(val: &str) -> Result<Self> {
+        match val {
+            "ndjson" | "json" => Ok(Self::Ndjson),
+            "csv" => Ok(Self::Csv),
+            _ => Err(io::Error::new(io::ErrorKind::InvalidInput, format!("unknown format {val}, expected ndjson or csv")))
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Record<'a> {
+    timestamp: String,
+    pbx: &'a str,
+    exten: &'a str,
+    old_status: i8,
+    new_status: i8,
+    old_status_text: &'a str,
+    status_text: &'a str
+}
+
+impl<'a> From<&'a StatusChange> for Record<'a> {
+    fn from(change: &'a StatusChange) -> Self {
+        Self {
+            timestamp: rfc3339(change.at),
+            pbx: &change.pbx,
+            exten: &change.exten,
+            old_status: change.old.status,
+            new_status: change.new.status,
+            old_status_text: &change.old.status_text,
+            status_text: &change.new.status_text
+        }
+    }
+}
+
+/// Writes a line per status change until stdout is closed (`| head`, ...).
+pub fn run_stream(monitor: &Monitor, format: Format) -> Result<()> {
+    match write_stream(monitor.subscribe(), &mut io::stdout().lock(), format) {
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        x => x
+    }
+}
+
+fn write_stream(changes: Receiver<MonitorEvent>, out: &mut impl Write, format: Format) -> Result<()> {
+    if format == Format::Csv {
+        writeln!(out, "timestamp,pbx,exten,old_status,new_status,old_status_text,status_text")?;
+        out.flush()?;
+    }
+
+    for event in changes {
+        let MonitorEvent::Status(change) = event else { continue };
+        let record = Record::from(&change);
+
+        match format {
+            Format::Ndjson => writeln!(out, "{}", serde_json::to_string(&record)?)?,
+            Format::Csv => writeln!(
+                out,
+                "{},{},{},{},{},{},{}",
+                record.timestamp, csv(record.pbx), csv(record.exten), record.old_status, record.new_status, csv(record.old_status_text), csv(record.status_text)
+            )?
+        }
+        out.flush()?;
+    }
+
+    Ok(())
+}
+
+/// Quotes a CSV field when needed.
+fn csv(val: &str) -> String {
+    if val.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", val.replace('"', "\"\""))
+    } else {
+        val.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(exten: &str, text: &str) -> MonitorEvent {
+        let status = |status, text: &str| SipStatus { status, status_text: text.to_owned(), presence: None };
+
+        MonitorEvent::Status(StatusChange { id: 1, pbx: "pbx".to_owned(), exten: exten.to_owned(), old: status(0, "Idle"), new: status(1, text), at: 0 })
+    }
+
+    fn written(format: Format, events: Vec<MonitorEvent>) -> String {
+        let (send, recv) = mpsc::channel();
+        for event in events {
+            send.send(event).unwrap();
+        }
+        drop(send);
+
+        let mut out = Vec::new();
+        write_stream(recv, &mut out, format).unwrap();
+
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("ndjson".parse::<Format>().unwrap(), Format::Ndjson);
+        assert_eq!("json".parse::<Format>().unwrap(), Format::Ndjson);
+        assert_eq!("csv".parse::<Format>().unwrap(), Format::Csv);
+        assert_eq!("xml".parse::<Format>().unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn quotes_csv_fields_only_when_needed() {
+        assert_eq!(csv("InUse"), "InUse");
+        assert_eq!(csv("Busy, DND"), "\"Busy, DND\"");
+        assert_eq!(csv("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn writes_csv_with_a_header() {
+        let out = written(Format::Csv, vec![change("2001", "In use, on hold")]);
+
+        assert_eq!(out, "timestamp,pbx,exten,old_status,new_status,old_status_text,status_text\n\
+            1970-01-01T00:00:00Z,pbx,2001,0,1,Idle,\"In use, on hold\"\n");
+    }
+
+    #[test]
+    fn writes_a_json_object_per_line() {
+        let out = written(Format::Ndjson, vec![change("2001", "InUse"), change("2002", "say \"hi\"")]);
+        let lines = out.lines().map(|x| serde_json::from_str::<serde_json::Value>(x).unwrap()).collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["timestamp"], "1970-01-01T00:00:00Z");
+        assert_eq!(lines[1]["status_text"], "say \"hi\"");
+    }
+}
@@ -0,0 +1,203 @@
+use crate::*;
+use std::str::FromStr;
+
+/// Theme of the tiles when the settings name none, or one that doesn't exist.
+pub const DEFAULT_THEME: &str = "classic";
+
+/// A color written `#rrggbb` in the settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl FromStr for Rgb {
+    type Err = String;
+
+    fn from_str(val: &str) -> std::result::Result<Self, Self::Err> {
+        let hex = val.trim().trim_start_matches('#');
+        let channel = |idx: usize| hex.get(idx..idx + 2).and_then(|x| u8::from_str_radix(x, 16).ok());
+
+        match (hex.len(), channel(0), channel(2), channel(4)) {
+            (6, Some(r), Some(g), Some(b)) => Ok(Self(r, g, b)),
+            _ => Err(format!("invalid color {val}, expected #rrggbb"))
+        }
+    }
+}
+
+impl TryFrom<String> for Rgb {
+    type Error = String;
+
+    fn try_from(val: String) -> std::result::Result<Self, Self::Error> {
+        val.parse()
+    }
+}
+
+impl From<Rgb> for String {
+    fn from(val: Rgb) -> Self {
+        val.to_string()
+    }
+}
+
+impl Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TileColors {
+    pub fill: Rgb,
+    pub text: Rgb
+}
+
+/// Colors of the tiles by extension state.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Theme {
+    pub name: String,
+    /// States left out take the colors of the closest one, see `Theme::colors`
+    pub statuses: HashMap<ExtensionState, TileColors>
+}
+
+impl Theme {
+    /// `classic` and `alternate` are the palettes the dashboard always had, with readable text;
+    /// `colorblind` follows Okabe and Ito, `high_contrast` keeps every pair above 7:1.
+    pub fn builtin() -> Vec<Self> {
+        use ExtensionState::*;
+
+        let white = Rgb(0xff, 0xff, 0xff);
+        let black = Rgb(0, 0, 0);
+
+        vec![
+            Self::new("classic", [
+                (Idle, Rgb(0, 100, 0), white),
+                (InUse, Rgb(0, 0, 139), white),
+                (Busy, Rgb(139, 0, 0), white),
+                (Ringing, Rgb(255, 255, 0), black),
+                (OnHold, Rgb(165, 42, 42), white),
+                (Unknown, Rgb(96, 96, 96), white)
+            ]),
+            Self::new("alternate", [
+                (Idle, Rgb(0, 0, 139), white),
+                (InUse, Rgb(255, 215, 0), black),
+                (Busy, Rgb(139, 0, 0), white),
+                (Ringing, Rgb(255, 255, 0), black),
+                (OnHold, Rgb(240, 230, 140), black),
+                (Unknown, Rgb(96, 96, 96), white)
+            ]),
+            Self::new("colorblind", [
+                (Idle, Rgb(0x00, 0x9e, 0x73), black),
+                (InUse, Rgb(0x00, 0x72, 0xb2), white),
+                (Busy, Rgb(0xd5, 0x5e, 0x00), black),
+                (Ringing, Rgb(0xf0, 0xe4, 0x42), black),
+                (OnHold, Rgb(0xcc, 0x79, 0xa7), black),
+                (Unavailable, Rgb(0x3a, 0x3a, 0x3a), white),
+                (Unknown, Rgb(0x99, 0x99, 0x99), black)
+            ]),
+            Self::new("high_contrast", [
+                (Idle, Rgb(0x00, 0xff, 0x00), black),
+                (InUse, Rgb(0x00, 0x00, 0xc0), white),
+                (Busy, Rgb(0xff, 0x70, 0x70), black),
+                (Ringing, Rgb(0xff, 0xff, 0x00), black),
+                (OnHold, Rgb(0xff, 0x80, 0xff), black),
+                (Unavailable, black, white),
+                (Unknown, white, black)
+            ])
+        ]
+    }
+
+    /// `themes` followed by the built in ones they don't replace.
+    pub fn with_builtin(themes: &[Self]) -> Vec<Self> {
+        let mut all = themes.to_vec();
+
+        for builtin in Self::builtin() {
+            if !all.iter().any(|x| x.name == builtin.name) {
+                all.push(builtin);
+            }
+        }
+
+        all
+    }
+
+    fn new<const N: usize>(name: &str, statuses: [(ExtensionState, Rgb, Rgb); N]) -> Self {
+        Self {
+            name: name.to_owned(),
+            statuses: statuses.into_iter().map(|(state, fill, text)| (state, TileColors { fill, text })).collect()
+        }
+    }
+
+    /// Colors of `state`, or of the state it's closest to when the theme leaves it out.
+    pub fn colors(&self, state: ExtensionState) -> TileColors {
+        use ExtensionState::*;
+
+        let fallback = match state {
+            InUseRinging => InUse,
+            InUseOnHold => OnHold,
+            Unavailable => Busy,
+            _ => Unknown
+        };
+
+        self.statuses.get(&state)
+            .or_else(|| self.statuses.get(&fallback))
+            .or_else(|| self.statuses.get(&Unknown))
+            .copied()
+            .unwrap_or(TileColors { fill: Rgb(96, 96, 96), text: Rgb(0xff, 0xff, 0xff) })
+    }
+}
+
+impl ExtensionState {
+    /// Shown next to the colors, so the state doesn't depend on telling them apart.
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Idle => "✔",
+            Self::InUse => "☎",
+            Self::Busy => "⛔",
+            Self::Unavailable => "✖",
+            Self::Ringing => "🔔",
+            Self::InUseRinging => "☎🔔",
+            Self::OnHold | Self::InUseOnHold => "⏸",
+            Self::Removed | Self::Unknown => "❓"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors_are_hex() {
+        assert_eq!("#00ff7F".parse(), Ok(Rgb(0, 0xff, 0x7f)));
+        assert_eq!(" 0072b2 ".parse(), Ok(Rgb(0, 0x72, 0xb2)));
+        assert!("#fff".parse::<Rgb>().is_err());
+        assert!("#gg0000".parse::<Rgb>().is_err());
+        assert!("#00ff7f00".parse::<Rgb>().is_err());
+        assert_eq!(Rgb(0, 0xff, 0x7f).to_string(), "#00ff7f");
+    }
+
+    #[test]
+    fn themes_from_the_settings() {
+        let theme: Theme = serde_json::from_str(r##"{"name": "night", "statuses": {"idle": {"fill": "#003300", "text": "#ffffff"}}}"##).unwrap();
+
+        assert_eq!(theme.colors(ExtensionState::Idle).fill, Rgb(0, 0x33, 0));
+        assert!(serde_json::from_str::<Theme>(r##"{"name": "bad", "statuses": {"idle": {"fill": "green", "text": "#ffffff"}}}"##).is_err());
+        assert_eq!(serde_json::to_string(&TileColors { fill: Rgb(1, 2, 3), text: Rgb(0xff, 0xff, 0xff) }).unwrap(), r##"{"fill":"#010203","text":"#ffffff"}"##);
+
+        // A theme of the settings replaces the built in one of the same name
+        let classic = Theme { name: DEFAULT_THEME.to_owned(), ..theme };
+        let all = Theme::with_builtin(&[classic]);
+        assert_eq!(all.len(), Theme::builtin().len());
+        assert_eq!(all[0].colors(ExtensionState::Idle).fill, Rgb(0, 0x33, 0));
+    }
+
+    #[test]
+    fn missing_states_take_the_closest_colors() {
+        let classic = &Theme::builtin()[0];
+
+        assert_eq!(classic.colors(ExtensionState::InUseRinging), classic.colors(ExtensionState::InUse));
+        assert_eq!(classic.colors(ExtensionState::InUseOnHold), classic.colors(ExtensionState::OnHold));
+        assert_eq!(classic.colors(ExtensionState::Unavailable), classic.colors(ExtensionState::Busy));
+        assert_eq!(classic.colors(ExtensionState::Removed), classic.colors(ExtensionState::Unknown));
+
+        let empty = Theme { name: "empty".to_owned(), statuses: HashMap::new() };
+        assert_eq!(empty.colors(ExtensionState::Idle).fill, Rgb(96, 96, 96));
+    }
+}
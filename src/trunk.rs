@@ -0,0 +1,184 @@
+use crate::*;
+
+/// Events the trunks strip consumes, on top of `CONTACT_EVENTS`.
+pub const TRUNK_EVENTS: &[&str] = &["Registry"];
+
+/// Trunks keyed by their endpoint.
+pub type Trunks = Arc<Mutex<BTreeMap<String, Trunk>>>;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrunkState {
+    Registered,
+    Rejected,
+    /// Every qualified contact of the trunk stopped answering
+    Unreachable,
+    Unregistered,
+    /// Answers the qualify, for trunks that don't register
+    Reachable,
+    #[default]
+    Unknown
+}
+
+impl Display for TrunkState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct Trunk {
+    pub endpoint: String,
+    /// Outbound registration of the trunk, `None` when it doesn't register
+    pub registration: Option<String>,
+    pub server_uri: String,
+    pub client_uri: String,
+    /// `Status` of the last `OutboundRegistrationDetail` or `Registry` event
+    pub registry: String,
+    /// Reason given by the provider along with the status, if any
+    pub cause: String,
+    /// Channel limit from the settings
+    pub limit: Option<usize>,
+    pub state: TrunkState,
+    /// Unix timestamp of the last state change, zero while it's unknown
+    pub changed: u64
+}
+
+impl Trunk {
+    fn new(endpoint: &str) -> Self {
+        Self { endpoint: endpoint.to_owned(), ..Default::default() }
+    }
+
+    /// Channels of the trunk, out of `limit`.
+    pub fn usage(&self, channels: &BTreeMap<String, Channel>) -> usize {
+        channels.values().filter(|x| x.endpoint() == self.endpoint).count()
+    }
+
+    /// The qualify takes precedence: a registered trunk that stopped answering is down all the same.
+    fn current(&self, regs: &BTreeMap<String, Registration>) -> TrunkState {
+        let mut contacts = regs.values().filter(|x| x.aor == self.endpoint).map(|x| x.status).peekable();
+        let qualified = contacts.peek().is_some();
+        let unreachable = contacts.all(|x| x == Reachability::Unreachable);
+
+        match (&self.registration, &self.registry[..]) {
+            _ if qualified && unreachable => TrunkState::Unreachable,
+            (Some(_), "Registered") => TrunkState::Registered,
+            (Some(_), "Rejected" | "Failed") => TrunkState::Rejected,
+            (Some(_), "") => TrunkState::Unknown,
+            (Some(_), _) => TrunkState::Unregistered,
+            (None, _) if qualified => TrunkState::Reachable,
+            (None, _) => TrunkState::Unknown
+        }
+    }
+}
+
+/// Adds the trunks of the settings, which may not register and so wouldn't show up otherwise.
+pub fn configure_trunks(trunks: &Trunks, regs: &Registrations, settings: &[TrunkSettings]) {
+    {
+        let mut trunks = trunks.lock().unwrap();
+
+        for conf in settings {
+            trunks.entry(conf.endpoint.clone()).or_insert_with(|| Trunk::new(&conf.endpoint)).limit = conf.limit;
+        }
+    }
+
+    refresh(trunks, regs);
+}
+
+/// Updates `trunks` from `OutboundRegistrationDetail` and `Registry` events, and their state
+/// from `regs` on every contact event. Must run after `treat_registrations`.
+pub fn treat_trunks(val: &str, trunks: &Trunks, regs: &Registrations) {
+    match val.as_bytes() {
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'O', b'u', b't', b'b', b'o', b'u', b'n', b'd', b'R', b'e', b'g', b'i', b's', b't', b'r', b'a', b't', b'i', b'o', b'n', b'D', b'e', b't', b'a', b'i', b'l', b'\r', ..] => {
+            let v = get_map(val);
+            let Some(name) = v.get("ObjectName") else { return };
+            // Asterisk before 16 doesn't tell the endpoint, FreePBX names both after the trunk anyway
+            let endpoint = v.get("Endpoint").filter(|x| !x.is_empty()).unwrap_or(name);
+            let mut trunks = trunks.lock().unwrap();
+            let trunk = trunks.entry(endpoint.to_string()).or_insert_with(|| Trunk::new(endpoint));
+
+            trunk.registration = Some(name.to_string());
+            trunk.server_uri = v.get("ServerUri").unwrap_or(&"").to_string();
+            trunk.client_uri = v.get("ClientUri").unwrap_or(&"").to_string();
+            trunk.registry = v.get("Status").unwrap_or(&"").to_string();
+        },
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'R', b'e', b'g', b'i', b's', b't', b'r', b'y', b'\r', ..] => {
+            let v = get_map(val);
+            // `Username: sip:user@provider`, `Domain: sip:provider`
+            let (Some(username), Some(domain)) = (v.get("Username"), v.get("Domain")) else { return };
+            let mut trunks = trunks.lock().unwrap();
+            let registered = || trunks.values().filter(|x| x.registration.is_some());
+            // Trunks on the same provider share the domain, only the user tells them apart
+            let key = match registered().find(|x| x.client_uri == *username) {
+                Some(trunk) => trunk.endpoint.clone(),
+                None => {
+                    let mut same = registered().filter(|x| x.server_uri == *domain);
+                    let (Some(trunk), None) = (same.next(), same.next()) else { return };
+                    trunk.endpoint.clone()
+                }
+            };
+            let Some(trunk) = trunks.get_mut(&key) else { return };
+
+            trunk.registry = v.get("Status").unwrap_or(&"").to_string();
+            trunk.cause = v.get("Cause").unwrap_or(&"").to_string();
+        },
+        [b'E', b'v', b'e', b'n', b't', b':', b' ', b'C', b'o', b'n', b't', b'a', b'c', b't', ..] => (),
+        _ => return
+    }
+
+    refresh(trunks, regs);
+}
+
+fn refresh(trunks: &Trunks, regs: &Registrations) {
+    let regs = regs.lock().unwrap();
+    let now = unix_now();
+
+    for trunk in trunks.lock().unwrap().values_mut() {
+        let state = trunk.current(&regs);
+
+        if state != trunk.state {
+            trunk.state = state;
+            trunk.changed = now;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outbound(trunk: &str, user: &str) -> String {
+        format!("Event: OutboundRegistrationDetail\r\nObjectName: {trunk}\r\nEndpoint: {trunk}\r\nServerUri: sip:sip.provider.com\r\nClientUri: sip:{user}@sip.provider.com\r\nStatus: Unregistered\r\n")
+    }
+
+    fn registry(user: &str, status: &str) -> String {
+        format!("Event: Registry\r\nChannelType: PJSIP\r\nUsername: sip:{user}@sip.provider.com\r\nDomain: sip:sip.provider.com\r\nStatus: {status}\r\nCause: 403 Forbidden\r\n")
+    }
+
+    #[test]
+    fn registry_goes_to_the_trunk_of_its_user() {
+        let (trunks, regs) = (Trunks::default(), Registrations::default());
+        treat_trunks(&outbound("main", "1001"), &trunks, &regs);
+        treat_trunks(&outbound("backup", "1002"), &trunks, &regs);
+
+        treat_trunks(&registry("1002", "Rejected"), &trunks, &regs);
+
+        let trunks = trunks.lock().unwrap();
+        assert_eq!((trunks["main"].state, &trunks["main"].cause[..]), (TrunkState::Unregistered, ""));
+        assert_eq!((trunks["backup"].state, &trunks["backup"].cause[..]), (TrunkState::Rejected, "403 Forbidden"));
+    }
+
+    #[test]
+    fn registry_falls_back_to_the_domain_only_when_unambiguous() {
+        let (trunks, regs) = (Trunks::default(), Registrations::default());
+        treat_trunks(&outbound("main", "1001"), &trunks, &regs);
+
+        treat_trunks(&registry("other", "Registered"), &trunks, &regs);
+        assert_eq!(trunks.lock().unwrap()["main"].state, TrunkState::Registered);
+
+        treat_trunks(&outbound("backup", "1002"), &trunks, &regs);
+        treat_trunks(&registry("other", "Rejected"), &trunks, &regs);
+        assert_eq!(trunks.lock().unwrap()["main"].state, TrunkState::Registered);
+        assert_eq!(trunks.lock().unwrap()["backup"].state, TrunkState::Unregistered);
+    }
+}
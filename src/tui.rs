@@ -0,0 +1,256 @@
+use crate::*;
+use std::sync::atomic::Ordering::Relaxed;
+use crossterm::{
+    cursor,
+    event::{
+        self,
+        Event,
+        KeyCode,
+        KeyEvent,
+        KeyEventKind,
+        KeyModifiers
+    },
+    style::{
+        Color,
+        Print,
+        ResetColor,
+        SetBackgroundColor,
+        SetForegroundColor
+    },
+    terminal::{
+        self,
+        Clear,
+        ClearType
+    },
+    queue,
+    execute
+};
+
+/// Columns taken by each tile, gap included.
+const TILE: u16 = 22;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum Sort {
+    #[default]
+    Exten,
+    Status,
+    StatusText
+}
+
+impl Sort {
+    fn next(self) -> Self {
+        match self {
+            Self::Exten => Self::Status,
+            Self::Status => Self::StatusText,
+            Self::StatusText => Self::Exten
+        }
+    }
+}
+
+#[derive(Default)]
+struct Console {
+    themes: Vec<Theme>,
+    /// Index in `themes`
+    theme: usize,
+    sort: Sort,
+    filter: String,
+    editing: bool,
+    /// First visible row of tiles
+    scroll: usize,
+    /// Last line logged since the screen was taken, e.g. a failed action
+    log: String
+}
+
+/// Restores the terminal even if drawing fails or panics.
+struct Screen;
+
+impl Screen {
+    /// Takes the terminal; the lines logged meanwhile go to the returned receiver instead.
+    fn enter() -> Result<(Self, Receiver<String>)> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+
+        let (send, logs) = mpsc::channel();
+        set_log(Some(send));
+
+        Ok((Self, logs))
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        set_log(None);
+        let _ = execute!(io::stdout(), ResetColor, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Same themes as the GUI tiles, as (background, text).
+fn colors(theme: &Theme, state: ExtensionState) -> (Color, Color) {
+    let rgb = |val: Rgb| Color::Rgb { r: val.0, g: val.1, b: val.2 };
+    let colors = theme.colors(state);
+
+    (rgb(colors.fill), rgb(colors.text))
+}
+
+/// Full screen monitor of `monitor`, until `q` or `Ctrl+C`.
+pub fn run_console(monitor: &Monitor, settings: &Settings) -> Result<()> {
+    let (_screen, logs) = Screen::enter()?;
+    let changes = monitor.subscribe();
+    let themes = Theme::with_builtin(&settings.themes);
+    let theme = themes.iter()
+        .position(|x| x.name == settings.theme)
+        .or_else(|| themes.iter().position(|x| x.name == DEFAULT_THEME))
+        .unwrap_or_default();
+    let mut console = Console { themes, theme, ..Default::default() };
+    let mut dirty = true;
+
+    loop {
+        while changes.try_recv().is_ok() {
+            dirty = true;
+        }
+        // Frames logged whole, e.g. an error reply, on one line
+        while let Ok(line) = logs.try_recv() {
+            console.log = line.split_whitespace().collect::<Vec<_>>().join(" ");
+            dirty = true;
+        }
+
+        if dirty {
+            draw(monitor, &mut console)?;
+            dirty = false;
+        }
+
+        // Redraws at least every second, for the connection state
+        if !event::poll(Duration::from_secs(1))? {
+            dirty = true;
+            continue
+        }
+
+        match event::read()? {
+            // Windows also reports releases
+            Event::Key(key) if key.kind != KeyEventKind::Release => {
+                if !console.key(key) {
+                    return Ok(())
+                }
+                dirty = true;
+            },
+            Event::Resize(..) => dirty = true,
+            _ => ()
+        }
+    }
+}
+
+impl Console {
+    /// Applies a key press, returning `false` to quit.
+    fn key(&mut self, key: KeyEvent) -> bool {
+        if self.editing {
+            match key.code {
+                KeyCode::Enter | KeyCode::Esc => self.editing = false,
+                KeyCode::Backspace => {
+                    self.filter.pop();
+                },
+                KeyCode::Char(c) => self.filter.push(c),
+                _ => ()
+            }
+            self.scroll = 0;
+
+            return true
+        }
+
+        match key.code {
+            KeyCode::Char('q') => return false,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Char('c') => self.theme = (self.theme + 1) % self.themes.len(),
+            KeyCode::Char('s') => self.sort = self.sort.next(),
+            KeyCode::Char('/') => self.editing = true,
+            KeyCode::Esc => self.filter.clear(),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll += 1,
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll += 10,
+            KeyCode::Home => self.scroll = 0,
+            KeyCode::End => self.scroll = usize::MAX,
+            _ => ()
+        }
+
+        true
+    }
+}
+
+fn draw(monitor: &Monitor, console: &mut Console) -> Result<()> {
+    let (width, height) = terminal::size()?;
+    let per_row = (width / TILE).max(1) as usize;
+    // The log line and the status bar below the tiles
+    let visible = height.saturating_sub(2) as usize;
+    let filter = console.filter.to_lowercase();
+    let data = monitor.data.lock().unwrap();
+    let mut tiles = data.iter()
+        .filter(|(k, v)| filter.is_empty() || k.to_lowercase().contains(&filter) || v.status_text.to_lowercase().contains(&filter))
+        .collect::<Vec<_>>();
+
+    match console.sort {
+        Sort::Exten => (),
+        Sort::Status => tiles.sort_by_key(|(_, v)| v.status),
+        Sort::StatusText => tiles.sort_by(|(_, a), (_, b)| a.status_text.cmp(&b.status_text))
+    }
+
+    let rows = (tiles.len() + per_row - 1) / per_row;
+    console.scroll = console.scroll.min(rows.saturating_sub(visible));
+
+    let mut out = io::stdout();
+    let theme = &console.themes[console.theme];
+    let shown = tiles.iter().skip(console.scroll * per_row).take(visible * per_row).collect::<Vec<_>>();
+
+    // Written over in place, clearing only what's left of each line, so nothing flickers
+    for row in 0..visible {
+        queue!(out, cursor::MoveTo(0, row as u16))?;
+
+        for (exten, status) in shown.iter().skip(row * per_row).take(per_row) {
+            let (bg, fg) = colors(theme, status.state());
+            let text = format!(" {} {exten:<6} {}", status.state().icon(), status.status_text);
+            let text = text.chars().take(TILE as usize - 1).collect::<String>();
+
+            queue!(
+                out,
+                SetBackgroundColor(bg),
+                SetForegroundColor(fg),
+                Print(format!("{text:<width$}", width = TILE as usize - 1)),
+                ResetColor,
+                Print(" ")
+            )?;
+        }
+
+        queue!(out, Clear(ClearType::UntilNewLine))?;
+    }
+
+    let log = console.log.chars().take(width as usize).collect::<String>();
+
+    queue!(
+        out,
+        cursor::MoveTo(0, height.saturating_sub(2)),
+        SetForegroundColor(Color::DarkGrey),
+        Print(log),
+        ResetColor,
+        Clear(ClearType::UntilNewLine)
+    )?;
+
+    let connected = monitor.stats.connected.load(Relaxed);
+    let calls = active_calls(&monitor.channels.lock().unwrap());
+    let filter = if console.editing { format!("/{}_", console.filter) } else { console.filter.clone() };
+    let bar = format!(
+        " {} {} | {} of {} ext | {calls} calls | sort: {:?} | filter: {filter} | theme: {} | q quit  / filter  s sort  c theme  arrows scroll",
+        monitor.pbx, if connected { "connected" } else { "reconnecting..." }, tiles.len(), data.len(), console.sort, console.themes[console.theme].name
+    );
+    let bar = bar.chars().take(width as usize).collect::<String>();
+
+    queue!(
+        out,
+        cursor::MoveTo(0, height.saturating_sub(1)),
+        SetBackgroundColor(if connected { Color::DarkBlue } else { Color::DarkRed }),
+        SetForegroundColor(Color::White),
+        Print(format!("{bar:<width$}", width = width as usize)),
+        ResetColor
+    )?;
+
+    out.flush()
+}
@@ -0,0 +1,144 @@
+use crate::*;
+use std::net::TcpStream;
+use sha1::{
+    Digest,
+    Sha1
+};
+use base64::{
+    engine::general_purpose::STANDARD,
+    Engine
+};
+
+/// Appended to the client key in the handshake (RFC 6455).
+const GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+/// Biggest message accepted from a client.
+const MAX_MESSAGE: usize = 1 << 20;
+
+/// Server side of a websocket. Only what the bridge needs: text messages, ping and close.
+pub struct WebSocket {
+    stream: TcpStream
+}
+
+/// Accepts the upgrade and runs `handler` on the socket, in the connection thread.
+pub fn websocket<F>(req: &Request, handler: F) -> Response
+    where F: FnOnce(WebSocket) -> Result<()> + Send + 'static
+{
+    let key = match (req.headers.get("upgrade"), req.headers.get("sec-websocket-key")) {
+        (Some(upgrade), Some(key)) if upgrade.eq_ignore_ascii_case("websocket") => key,
+        _ => return Response::new(400, "text/plain; charset=utf-8", "Expected a websocket upgrade")
+    };
+    let accept = STANDARD.encode(Sha1::digest(format!("{key}{GUID}")));
+
+    Response {
+        status: 101,
+        headers: Vec::new(),
+        body: Vec::new(),
+        stream: Some(Box::new(move |stream| {
+            // The request timeout would drop idle sockets
+            stream.set_read_timeout(None)?;
+
+            handler(WebSocket { stream: stream.try_clone()? })
+        }))
+    }
+    .header("Upgrade", "websocket")
+    .header("Connection", "Upgrade")
+    .header("Sec-WebSocket-Accept", &accept)
+}
+
+impl WebSocket {
+    /// Next text message, answering pings on the way. `None` once the client closes.
+    pub fn recv(&mut self) -> Result<Option<String>> {
+        let mut message = Vec::new();
+
+        loop {
+            let (fin, opcode, payload) = self.read_frame()?;
+
+            match opcode {
+                // Close, echoing the status code
+                0x8 => {
+                    let _ = self.write_frame(0x8, &payload[..payload.len().min(2)]);
+                    return Ok(None)
+                },
+                0x9 => self.write_frame(0xA, &payload)?,
+                0xA => (),
+                0x0 | 0x1 | 0x2 => {
+                    message.extend(payload);
+
+                    if message.len() > MAX_MESSAGE {
+                        return Err(io::Error::new(io::ErrorKind::InvalidData, "message too big"))
+                    }
+                    if fin {
+                        return String::from_utf8(message).map(Some).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+                    }
+                },
+                _ => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("unknown opcode {opcode}")))
+            }
+        }
+    }
+
+    pub fn send(&mut self, text: &str) -> Result<()> {
+        self.write_frame(0x1, text.as_bytes())
+    }
+
+    pub fn close(&mut self) -> Result<()> {
+        self.write_frame(0x8, &1000u16.to_be_bytes())
+    }
+
+    fn read_frame(&mut self) -> Result<(bool, u8, Vec<u8>)> {
+        let mut head = [0u8; 2];
+        self.stream.read_exact(&mut head)?;
+
+        let len = match head[1] & 0x7f {
+            126 => {
+                let mut len = [0u8; 2];
+                self.stream.read_exact(&mut len)?;
+                u16::from_be_bytes(len) as usize
+            },
+            127 => {
+                let mut len = [0u8; 8];
+                self.stream.read_exact(&mut len)?;
+                u64::from_be_bytes(len).try_into().unwrap_or(usize::MAX)
+            },
+            len => len as usize
+        };
+
+        if len > MAX_MESSAGE {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "frame too big"))
+        }
+
+        // Clients always mask
+        let mut mask = [0u8; 4];
+        if head[1] & 0x80 != 0 {
+            self.stream.read_exact(&mut mask)?;
+        }
+
+        let mut payload = vec![0u8; len];
+        self.stream.read_exact(&mut payload)?;
+
+        for (idx, byte) in payload.iter_mut().enumerate() {
+            *byte ^= mask[idx % 4];
+        }
+
+        Ok((head[0] & 0x80 != 0, head[0] & 0x0f, payload))
+    }
+
+    fn write_frame(&mut self, opcode: u8, payload: &[u8]) -> Result<()> {
+        let mut frame = vec![0x80 | opcode];
+
+        match payload.len() {
+            len @ 0..=125 => frame.push(len as u8),
+            len @ 126..=0xffff => {
+                frame.push(126);
+                frame.extend((len as u16).to_be_bytes());
+            },
+            len => {
+                frame.push(127);
+                frame.extend((len as u64).to_be_bytes());
+            }
+        }
+        frame.extend(payload);
+
+        self.stream.write_all(&frame)?;
+        self.stream.flush()
+    }
+}
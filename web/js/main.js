@@ -3,7 +3,8 @@ import init, { start } from "./sip_monitor.js";
 async function main() {
     await init()
 
-    start("tracevia")
+    // The bridge is expected on this host, pass { wsUrl: "wss://..." } to use another one
+    start("tracevia", {})
 
     let canvas = document.getElementById("tracevia")
     canvas.style.maxHeight = window.innerHeight